@@ -0,0 +1,140 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use futures::future;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::token;
+use serde_json::{json, Map, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{AccountKeysResponse, AccountResponse, ErrorResponse};
+use crate::query::{
+    balance_of, current_epoch, native_token, public_keys_by_index, query_as, MyErrorWrapper, RPCRequestType, RPCResult,
+};
+use crate::ServerState;
+
+fn address_kind(address: &Address) -> &'static str {
+    match address {
+        Address::Implicit(_) => "implicit",
+        Address::Established(_) => "established",
+        Address::Internal(_) => "internal",
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/account/{address}",
+    params(("address" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Everything known about an address in one call: kind, balances, keys, roles and bonds.", body = AccountResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_account<C: RpcClient>(State(state): State<ServerState<C>>,
+                                       Path(address): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (epoch, native_token, masp_tokens, account, is_validator, is_delegator, is_steward, bonds) = futures::try_join!(
+        current_epoch(client),
+        native_token(client),
+        query_as(client, RPCRequestType::QueryMaspReward, |result| match result {
+            RPCResult::MapsReward(rewards) => Some(rewards),
+            _ => None,
+        }),
+        query_as(client, RPCRequestType::QueryAccountInfo(address.clone()), |result| match result {
+            RPCResult::AccountInfo(account) => Some(account),
+            _ => None,
+        }),
+        query_as(client, RPCRequestType::QueryCheckIsValidator(address.clone()), |result| match result {
+            RPCResult::IsValidator(is_validator) => Some(is_validator),
+            _ => None,
+        }),
+        query_as(client, RPCRequestType::QueryCheckIsDelegator(address.clone()), |result| match result {
+            RPCResult::IsDelegator(is_delegator) => Some(is_delegator),
+            _ => None,
+        }),
+        query_as(client, RPCRequestType::QueryCheckIsSteward(address.clone()), |result| match result {
+            RPCResult::IsSteward(is_steward) => Some(is_steward),
+            _ => None,
+        }),
+        query_as(client, RPCRequestType::QueryBondsAndUnbonds(Some(address.clone()), None), |result| match result {
+            RPCResult::BondsAndUnbonds(details) => Some(details),
+            _ => None,
+        }),
+    )?;
+
+    // The native token first, then every token the MASP rewards
+    let mut tokens = vec![native_token];
+    for reward in masp_tokens {
+        if !tokens.contains(&reward.address) {
+            tokens.push(reward.address);
+        }
+    }
+    let amounts = future::try_join_all(tokens.iter().map(|token| balance_of(client, token, address.clone()))).await?;
+    let balances = tokens
+        .iter()
+        .zip(amounts)
+        .map(|(token, amount)| (token.to_string(), json!(amount)))
+        .collect::<Map<_, _>>();
+
+    let mut total_bonded = token::Amount::zero();
+    let mut pending_unbonds = Vec::new();
+    for (bond_id, detail) in &bonds {
+        for bond in &detail.bonds {
+            total_bonded += bond.amount.checked_sub(bond.slashed_amount.unwrap_or_default()).unwrap_or_default();
+        }
+        for unbond in detail.unbonds.iter().filter(|unbond| unbond.withdraw > epoch) {
+            pending_unbonds.push(json!({
+                "validator": bond_id.validator,
+                "amount": unbond.amount,
+                "withdrawable_epoch": unbond.withdraw,
+            }));
+        }
+    }
+
+    Ok(Json(json!({
+        "address": address,
+        "kind": address_kind(&address),
+        "epoch": epoch,
+        "balances": balances,
+        "public_keys": account.as_ref().map(public_keys_by_index),
+        "threshold": account.as_ref().map(|account| account.threshold),
+        "roles": {
+            "validator": is_validator,
+            "delegator": is_delegator,
+            "steward": is_steward,
+        },
+        "total_bonded": total_bonded,
+        "pending_unbonds": pending_unbonds,
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/account/{address}/keys",
+    params(("address" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "An account's public keys, signing threshold and validity predicate code hash.", body = AccountKeysResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_account_keys<C: RpcClient>(State(state): State<ServerState<C>>,
+                                            Path(address): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (account, vp_code_hash) = futures::try_join!(
+        query_as(client, RPCRequestType::QueryAccountInfo(address.clone()), |result| match result {
+            RPCResult::AccountInfo(account) => Some(account),
+            _ => None,
+        }),
+        query_as(client, RPCRequestType::QueryVpCodeHash(address.clone()), |result| match result {
+            RPCResult::VpCodeHash(hash) => Some(hash),
+            _ => None,
+        }),
+    )?;
+
+    Ok(Json(json!({
+        "address": address,
+        "kind": address_kind(&address),
+        "threshold": account.as_ref().map(|account| account.threshold),
+        "public_keys": account.as_ref().map(public_keys_by_index).unwrap_or_default(),
+        "vp_code_hash": vp_code_hash.map(|hash| hash.to_string()),
+    })))
+}
@@ -0,0 +1,129 @@
+use axum::{extract::State, Json};
+use futures::stream::{self, StreamExt};
+use namada_sdk::error::{self, EncodingError};
+use namada_sdk::state::{BlockHeight, Epoch};
+use namada_sdk::types::address::Address;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::client::RpcClient;
+use crate::openapi::{BatchResponse, ErrorResponse};
+use crate::query::{constants, get_rpc_data, MyErrorWrapper, RPCRequestType};
+use crate::ServerState;
+
+/// One entry of a `POST /batch` body. The `query` tag picks the `RPCRequestType`, the other
+/// fields are the same parameters the matching GET route takes in its path.
+#[derive(Deserialize)]
+#[serde(tag = "query", rename_all = "snake_case")]
+pub enum BatchQuery {
+    Epoch,
+    EpochAtHeight { height: BlockHeight },
+    ProposalResult { id: u64 },
+    ProposalVotes { id: u64 },
+    Balance { wallet: Address },
+    ValidatorState { address: Address, epoch: Option<Epoch> },
+    DelegatorDelegation { wallet: Address },
+    DelegatorDelegationAt { wallet: Address, epoch: Epoch },
+    Metadata { address: Address, epoch: Option<Epoch> },
+    GovernanceParameters,
+    PosParameters,
+    IsSteward { wallet: Address },
+    ValidatorConsensusKeys { wallet: Address },
+    TxEvent { tx_hash: String },
+    NativeToken,
+    LatestBlock,
+    IsValidator { wallet: Address },
+    IsDelegator { wallet: Address },
+    MaspReward,
+    TotalStaked { epoch: Epoch },
+    ValidatorStake { address: Address, epoch: Epoch },
+}
+
+impl BatchQuery {
+    fn into_request(self) -> Result<RPCRequestType, MyErrorWrapper> {
+        Ok(match self {
+            BatchQuery::Epoch => RPCRequestType::QueryEpoch,
+            BatchQuery::EpochAtHeight { height } => RPCRequestType::QueryEpochAtHeight(height),
+            BatchQuery::ProposalResult { id } => RPCRequestType::QueryProposalResult(id),
+            BatchQuery::ProposalVotes { id } => RPCRequestType::QueryProposalVotes(id),
+            BatchQuery::Balance { wallet } => {
+                let token = Address::decode(constants::NAAN_ADDRESS).map_err(|_| {
+                    MyErrorWrapper(error::Error::Encode(EncodingError::Decoding(
+                        "Error decoding address.".to_string(),
+                    )))
+                })?;
+                RPCRequestType::QueryBalance(token, wallet)
+            }
+            BatchQuery::ValidatorState { address, epoch } => RPCRequestType::QueryValidatorState(address, epoch),
+            BatchQuery::DelegatorDelegation { wallet } => RPCRequestType::QueryDelegatorDelegation(wallet),
+            BatchQuery::DelegatorDelegationAt { wallet, epoch } => RPCRequestType::QueryDelegatorDelegationAt(wallet, epoch),
+            BatchQuery::Metadata { address, epoch } => RPCRequestType::QueryMetaData(address, epoch),
+            BatchQuery::GovernanceParameters => RPCRequestType::QueryGovernanceParameters,
+            BatchQuery::PosParameters => RPCRequestType::QueryPosParameters,
+            BatchQuery::IsSteward { wallet } => RPCRequestType::QueryCheckIsSteward(wallet),
+            BatchQuery::ValidatorConsensusKeys { wallet } => RPCRequestType::QueryValidatorConsensusKeys(wallet),
+            BatchQuery::TxEvent { tx_hash } => RPCRequestType::QueryTxEvents(tx_hash),
+            BatchQuery::NativeToken => RPCRequestType::QueryNativeToken,
+            BatchQuery::LatestBlock => RPCRequestType::QueryLatestBlock,
+            BatchQuery::IsValidator { wallet } => RPCRequestType::QueryCheckIsValidator(wallet),
+            BatchQuery::IsDelegator { wallet } => RPCRequestType::QueryCheckIsDelegator(wallet),
+            BatchQuery::MaspReward => RPCRequestType::QueryMaspReward,
+            BatchQuery::TotalStaked { epoch } => RPCRequestType::QueryTotalStakedTokens(epoch),
+            BatchQuery::ValidatorStake { address, epoch } => RPCRequestType::QueryValidatorStaked(epoch, address),
+        })
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BatchRequest {
+    /// Query descriptors such as `{"query": "balance", "wallet": "tnam1..."}` or
+    /// `{"query": "validator_stake", "address": "tnam1...", "epoch": 23}`
+    #[schema(value_type = Vec<Object>)]
+    queries: Vec<Value>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/batch",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Results in request order, each either {\"ok\": <route body>} or {\"error\": <message>}.", body = BatchResponse),
+        (status = 400, description = "Too many queries in one batch", body = ErrorResponse),
+    )
+)]
+pub async fn post_batch<C: RpcClient>(
+    State(state): State<ServerState<C>>,
+    Json(request): Json<BatchRequest>,
+) -> Result<Json<Value>, (axum::http::StatusCode, Json<Value>)> {
+    if request.queries.len() > state.config.batch_max_queries {
+        return Err((
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("A batch may hold at most {} queries.", state.config.batch_max_queries) })),
+        ));
+    }
+
+    // `buffered` keeps the output in request order while running up to `batch_concurrency` at once
+    let results = stream::iter(request.queries)
+        .map(|query| {
+            let client = state.client.clone();
+            async move {
+                let result = match serde_json::from_value::<BatchQuery>(query) {
+                    Ok(query) => match query.into_request() {
+                        Ok(req_type) => get_rpc_data(client, req_type).await,
+                        Err(err) => Err(err),
+                    },
+                    Err(err) => return json!({ "error": format!("Invalid query: {}", err) }),
+                };
+                match result {
+                    Ok(Json(value)) => json!({ "ok": value }),
+                    Err(err) => json!({ "error": err.to_string() }),
+                }
+            }
+        })
+        .buffered(state.config.batch_concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    Ok(Json(json!({ "data": results })))
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use namada_sdk::queries::Client;
+
+use crate::query::{query_rpc, MyErrorWrapper, RPCRequestType, RPCResult};
+
+/// The node client the handlers query through. Any implementation of namada_sdk's
+/// `queries::Client` qualifies, so a WebSocket, caching, failover or test client can be
+/// swapped in for the default `HttpClient` without touching the handlers.
+pub trait RpcClient: Client + Clone + Send + Sync + 'static {}
+
+impl<C> RpcClient for C where C: Client + Clone + Send + Sync + 'static {}
+
+/// Type-erased handle on `query_rpc`, for long-lived parts of the server (the GraphQL schema,
+/// the WebSocket poller) that shouldn't have to be generic over the client.
+#[derive(Clone)]
+pub struct RpcQuerier(
+    Arc<dyn Fn(RPCRequestType) -> BoxFuture<'static, Result<RPCResult, MyErrorWrapper>> + Send + Sync>,
+);
+
+impl RpcQuerier {
+    pub fn new<C: RpcClient>(client: C) -> Self {
+        RpcQuerier(Arc::new(move |req_type| query_rpc(client.clone(), req_type).boxed()))
+    }
+
+    pub async fn query(&self, req_type: RPCRequestType) -> Result<RPCResult, MyErrorWrapper> {
+        (self.0)(req_type).await
+    }
+}
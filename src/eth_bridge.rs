@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use axum::extract::State;
+use axum::Json;
+use futures::future;
+use namada_sdk::error;
+use namada_sdk::eth_bridge::storage::whitelist;
+use namada_sdk::types::ethereum_events::EthAddress;
+use namada_sdk::types::storage::{Key, KeySeg};
+use serde_json::{json, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{BridgeContractsResponse, BridgePoolResponse, BridgeWhitelistResponse, ErrorResponse};
+use crate::query::{get_rpc_data, query_as, MyErrorWrapper, RPCRequestType, RPCResult};
+use crate::ServerState;
+
+#[utoipa::path(
+    get,
+    path = "/eth_bridge/pool",
+    responses(
+        (status = 200, description = "Transfers to Ethereum waiting in the bridge pool, with the gas fee each one pays.", body = BridgePoolResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_bridge_pool<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    get_rpc_data(state.client, RPCRequestType::QueryBridgePool).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/eth_bridge/contracts",
+    responses(
+        (status = 200, description = "Addresses of the Ethereum bridge contract and of the native token's ERC20 contract.", body = BridgeContractsResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_bridge_contracts<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    get_rpc_data(state.client, RPCRequestType::QueryBridgeContracts).await
+}
+
+// Whitelist entries live under `#<eth bridge>/whitelist/<erc20>/<field>`, so the prefix is any entry's key
+// without its last two segments
+fn whitelist_prefix() -> Result<Key, MyErrorWrapper> {
+    let entry = Key::from(&whitelist::Key {
+        asset: EthAddress([0; 20]),
+        suffix: whitelist::KeyType::Whitelisted,
+    });
+    entry
+        .parent()
+        .and_then(|asset| asset.parent())
+        .ok_or_else(|| MyErrorWrapper(error::Error::Other("Invalid bridge whitelist key.".to_string())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/eth_bridge/whitelist",
+    responses(
+        (status = 200, description = "ERC20 tokens known to the bridge with their whitelist status, minted supply and cap.", body = BridgeWhitelistResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_bridge_whitelist<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let prefix = whitelist_prefix()?;
+    let entries = query_as(client, RPCRequestType::QueryStoragePrefix(prefix.clone()), |result| match result {
+        RPCResult::StoragePrefix(entries) => Some(entries),
+        _ => None,
+    })
+    .await?;
+
+    let mut erc20s = entries
+        .iter()
+        .filter_map(|(key, _)| key.segments.get(prefix.segments.len()))
+        .filter_map(|segment| EthAddress::from_str(&segment.raw()).ok())
+        .collect::<Vec<_>>();
+    erc20s.sort();
+    erc20s.dedup();
+
+    let data = future::try_join_all(erc20s.into_iter().map(|erc20| async move {
+        query_as(client, RPCRequestType::QueryErc20FlowControl(erc20), |result| match result {
+            RPCResult::Erc20FlowControl(whitelisted, supply, cap) => Some(json!({
+                "erc20": erc20.to_canonical(),
+                "whitelisted": whitelisted,
+                "supply": supply,
+                "cap": cap,
+            })),
+            _ => None,
+        })
+        .await
+    }))
+    .await?;
+
+    Ok(Json(json!({ "data": data })))
+}
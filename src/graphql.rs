@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Error, Object, Result, Schema, SimpleObject};
+use axum::response::{Html, IntoResponse};
+use axum::{Extension, Json};
+use futures::future;
+use namada_sdk::state::{BlockHeight, Epoch};
+use namada_sdk::types::address::Address;
+use namada_sdk::types::token;
+use serde_json::Value;
+
+use crate::client::{RpcClient, RpcQuerier};
+use crate::query::{constants, serialize, validator_state_name, RPCRequestType, RPCResult};
+use crate::Settings;
+
+pub type NamadaSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn build_schema<C: RpcClient>(client: C, config: &Settings) -> NamadaSchema {
+    let querier = RpcQuerier::new(client);
+    // Every field can cost an upstream query, so bound a request the way `POST /batch` is bounded
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(RpcLoader(querier.clone()), tokio::spawn))
+        .data(querier)
+        .limit_depth(config.graphql_max_depth)
+        .limit_complexity(config.graphql_max_complexity)
+        .finish()
+}
+
+pub async fn graphql_handler(
+    Extension(schema): Extension<NamadaSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+pub async fn graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// Batches the lookups that a list of accounts or validators fans out into, so that e.g.
+/// `delegations { validator { stake } }` resolves the current epoch once instead of per item.
+pub struct RpcLoader(RpcQuerier);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CurrentEpoch;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct NativeBalance(Address);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ValidatorStake(Address, Epoch);
+
+impl RpcLoader {
+    async fn load_all<K, V, F>(&self, keys: &[K], to_request: F) -> Result<HashMap<K, V>, Arc<String>>
+    where
+        K: Clone + Eq + std::hash::Hash,
+        V: TryFrom<RPCResult>,
+        F: Fn(&K) -> Result<RPCRequestType, Arc<String>>,
+    {
+        let requests = keys
+            .iter()
+            .map(|key| to_request(key).map(|req_type| (key.clone(), req_type)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let results = future::join_all(requests.into_iter().map(|(key, req_type)| async move {
+            let result = self.0.query(req_type).await.map_err(|err| Arc::new(err.to_string()))?;
+            let value = V::try_from(result).map_err(|_| Arc::new("unexpected RPC result".to_string()))?;
+            Ok::<_, Arc<String>>((key, value))
+        }))
+        .await;
+        results.into_iter().collect()
+    }
+}
+
+impl Loader<CurrentEpoch> for RpcLoader {
+    type Value = Epoch;
+    type Error = Arc<String>;
+
+    async fn load(&self, keys: &[CurrentEpoch]) -> Result<HashMap<CurrentEpoch, Epoch>, Self::Error> {
+        self.load_all(keys, |_| Ok(RPCRequestType::QueryEpoch)).await
+    }
+}
+
+impl Loader<NativeBalance> for RpcLoader {
+    type Value = token::Amount;
+    type Error = Arc<String>;
+
+    async fn load(&self, keys: &[NativeBalance]) -> Result<HashMap<NativeBalance, token::Amount>, Self::Error> {
+        let native_token = Address::decode(constants::NAAN_ADDRESS)
+            .map_err(|_| Arc::new("Error decoding address.".to_string()))?;
+        self.load_all(keys, |NativeBalance(owner)| {
+            Ok(RPCRequestType::QueryBalance(native_token.clone(), owner.clone()))
+        })
+        .await
+    }
+}
+
+impl Loader<ValidatorStake> for RpcLoader {
+    type Value = token::Amount;
+    type Error = Arc<String>;
+
+    async fn load(&self, keys: &[ValidatorStake]) -> Result<HashMap<ValidatorStake, token::Amount>, Self::Error> {
+        self.load_all(keys, |ValidatorStake(address, epoch)| {
+            Ok(RPCRequestType::QueryValidatorStaked(*epoch, address.clone()))
+        })
+        .await
+    }
+}
+
+impl TryFrom<RPCResult> for Epoch {
+    type Error = ();
+
+    fn try_from(result: RPCResult) -> Result<Self, ()> {
+        match result {
+            RPCResult::Epoch(epoch) => Ok(epoch),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<RPCResult> for token::Amount {
+    type Error = ();
+
+    fn try_from(result: RPCResult) -> Result<Self, ()> {
+        match result {
+            RPCResult::BalanceResult(amount)
+            | RPCResult::TotalStakedTokens(amount)
+            | RPCResult::ValidatorStake(amount)
+            | RPCResult::Rewards(amount) => Ok(amount),
+            _ => Err(()),
+        }
+    }
+}
+
+async fn query(ctx: &Context<'_>, req_type: RPCRequestType) -> Result<RPCResult> {
+    ctx.data_unchecked::<RpcQuerier>()
+        .query(req_type)
+        .await
+        .map_err(|err| Error::new(err.to_string()))
+}
+
+fn loader<'a>(ctx: &'a Context<'_>) -> &'a DataLoader<RpcLoader> {
+    ctx.data_unchecked::<DataLoader<RpcLoader>>()
+}
+
+async fn resolve_epoch(ctx: &Context<'_>, epoch: Option<u64>) -> Result<Epoch> {
+    match epoch {
+        Some(epoch) => Ok(Epoch(epoch)),
+        None => loader(ctx)
+            .load_one(CurrentEpoch)
+            .await?
+            .ok_or_else(|| Error::new("current epoch unavailable")),
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address> {
+    Address::decode(address).map_err(|_| Error::new(format!("Invalid address {}", address)))
+}
+
+fn unexpected() -> Error {
+    Error::new("unexpected RPC result")
+}
+
+// Render amounts exactly like the REST routes do
+fn amount_string(amount: token::Amount) -> String {
+    match serde_json::to_value(amount) {
+        Ok(Value::String(amount)) => amount,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The current epoch
+    async fn epoch(&self, ctx: &Context<'_>) -> Result<u64> {
+        Ok(resolve_epoch(ctx, None).await?.0)
+    }
+
+    /// The epoch of the given block height, if known
+    async fn epoch_at_height(&self, ctx: &Context<'_>, height: u64) -> Result<Option<u64>> {
+        match query(ctx, RPCRequestType::QueryEpochAtHeight(BlockHeight(height))).await? {
+            RPCResult::EpochAtHeight(epoch) => Ok(epoch.map(|epoch| epoch.0)),
+            _ => Err(unexpected()),
+        }
+    }
+
+    /// The last committed block
+    async fn latest_block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        match query(ctx, RPCRequestType::QueryLatestBlock).await? {
+            RPCResult::LatestBlock(block) => Ok(block.map(|block| Block {
+                height: block.height.0,
+                hash: block.hash.0.iter().map(|byte| format!("{:02X}", byte)).collect(),
+                time: block.time.to_rfc3339(),
+            })),
+            _ => Err(unexpected()),
+        }
+    }
+
+    /// The address of the native token
+    async fn native_token(&self, ctx: &Context<'_>) -> Result<String> {
+        match query(ctx, RPCRequestType::QueryNativeToken).await? {
+            RPCResult::NativeToken(address) => Ok(address.to_string()),
+            _ => Err(unexpected()),
+        }
+    }
+
+    async fn account(&self, address: String) -> Result<Account> {
+        Ok(Account { address: parse_address(&address)? })
+    }
+
+    async fn validator(&self, address: String) -> Result<Validator> {
+        Ok(Validator { address: parse_address(&address)? })
+    }
+
+    async fn proposal(&self, id: u64) -> Proposal {
+        Proposal { id }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Block {
+    height: u64,
+    hash: String,
+    time: String,
+}
+
+pub struct Account {
+    address: Address,
+}
+
+#[Object]
+impl Account {
+    async fn address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Balance of `token`, the native token when omitted
+    async fn balance(&self, ctx: &Context<'_>, token: Option<String>) -> Result<String> {
+        let amount = match token {
+            None => loader(ctx)
+                .load_one(NativeBalance(self.address.clone()))
+                .await?
+                .ok_or_else(unexpected)?,
+            Some(token) => {
+                let req_type = RPCRequestType::QueryBalance(parse_address(&token)?, self.address.clone());
+                token::Amount::try_from(query(ctx, req_type).await?).map_err(|_| unexpected())?
+            }
+        };
+        Ok(amount_string(amount))
+    }
+
+    /// Bonds of this account at `epoch`, the current epoch when omitted
+    async fn delegations(&self, ctx: &Context<'_>, epoch: Option<u64>) -> Result<Vec<Delegation>> {
+        let epoch = resolve_epoch(ctx, epoch).await?;
+        let req_type = RPCRequestType::QueryDelegatorDelegationAt(self.address.clone(), epoch);
+        match query(ctx, req_type).await? {
+            RPCResult::DelegatorDelegationAt(delegations) => Ok(delegations
+                .into_iter()
+                .map(|(validator, amount)| Delegation {
+                    delegator: self.address.clone(),
+                    validator,
+                    amount,
+                })
+                .collect()),
+            _ => Err(unexpected()),
+        }
+    }
+
+    async fn is_validator(&self, ctx: &Context<'_>) -> Result<bool> {
+        match query(ctx, RPCRequestType::QueryCheckIsValidator(self.address.clone())).await? {
+            RPCResult::IsValidator(result) => Ok(result),
+            _ => Err(unexpected()),
+        }
+    }
+
+    async fn is_delegator(&self, ctx: &Context<'_>) -> Result<bool> {
+        match query(ctx, RPCRequestType::QueryCheckIsDelegator(self.address.clone())).await? {
+            RPCResult::IsDelegator(result) => Ok(result),
+            _ => Err(unexpected()),
+        }
+    }
+
+    async fn is_steward(&self, ctx: &Context<'_>) -> Result<bool> {
+        match query(ctx, RPCRequestType::QueryCheckIsSteward(self.address.clone())).await? {
+            RPCResult::IsSteward(result) => Ok(result),
+            _ => Err(unexpected()),
+        }
+    }
+}
+
+pub struct Delegation {
+    delegator: Address,
+    validator: Address,
+    amount: token::Amount,
+}
+
+#[Object]
+impl Delegation {
+    async fn validator(&self) -> Validator {
+        Validator { address: self.validator.clone() }
+    }
+
+    async fn amount(&self) -> String {
+        amount_string(self.amount)
+    }
+
+    /// Unclaimed PoS rewards of this bond
+    async fn rewards(&self, ctx: &Context<'_>) -> Result<String> {
+        let req_type = RPCRequestType::QueryRewards(Some(self.delegator.clone()), self.validator.clone());
+        let amount = token::Amount::try_from(query(ctx, req_type).await?).map_err(|_| unexpected())?;
+        Ok(amount_string(amount))
+    }
+}
+
+pub struct Validator {
+    address: Address,
+}
+
+#[Object]
+impl Validator {
+    async fn address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Validator state at `epoch`, the current epoch when omitted
+    async fn state(&self, ctx: &Context<'_>, epoch: Option<u64>) -> Result<Option<String>> {
+        let epoch = resolve_epoch(ctx, epoch).await?;
+        let req_type = RPCRequestType::QueryValidatorState(self.address.clone(), Some(epoch));
+        match query(ctx, req_type).await? {
+            RPCResult::ValidatorState(state) => Ok(state.map(|state| validator_state_name(&state).to_string())),
+            _ => Err(unexpected()),
+        }
+    }
+
+    /// Bonded stake at `epoch`, the current epoch when omitted
+    async fn stake(&self, ctx: &Context<'_>, epoch: Option<u64>) -> Result<String> {
+        let epoch = resolve_epoch(ctx, epoch).await?;
+        let amount = loader(ctx)
+            .load_one(ValidatorStake(self.address.clone(), epoch))
+            .await?
+            .ok_or_else(unexpected)?;
+        Ok(amount_string(amount))
+    }
+
+    /// Commission at `epoch`, the current epoch when omitted
+    async fn commission(&self, ctx: &Context<'_>, epoch: Option<u64>) -> Result<Option<Commission>> {
+        let epoch = resolve_epoch(ctx, epoch).await?;
+        match query(ctx, RPCRequestType::QueryMetaData(self.address.clone(), Some(epoch))).await? {
+            RPCResult::MetaData((_, commission)) => Ok(commission.map(|commission| Commission {
+                commission_rate: commission.commission_rate.to_string(),
+                max_commission_change_per_epoch: commission.max_commission_change_per_epoch.to_string(),
+            })),
+            _ => Err(unexpected()),
+        }
+    }
+
+    /// Metadata at `epoch`, the current epoch when omitted
+    async fn metadata(&self, ctx: &Context<'_>, epoch: Option<u64>) -> Result<Option<Metadata>> {
+        let epoch = resolve_epoch(ctx, epoch).await?;
+        match query(ctx, RPCRequestType::QueryMetaData(self.address.clone(), Some(epoch))).await? {
+            RPCResult::MetaData((metadata, _)) => Ok(metadata.map(|metadata| Metadata {
+                email: metadata.email,
+                description: metadata.description,
+                website: metadata.website,
+                discord_handle: metadata.discord_handle,
+                avatar: metadata.avatar,
+            })),
+            _ => Err(unexpected()),
+        }
+    }
+
+    async fn consensus_key(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        match query(ctx, RPCRequestType::QueryValidatorConsensusKeys(self.address.clone())).await? {
+            RPCResult::ValidatorConsensusKeys(key) => Ok(serialize(&key, serde_json::value::Serializer)
+                .ok()
+                .and_then(|key| key.as_str().map(str::to_string))),
+            _ => Err(unexpected()),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Commission {
+    commission_rate: String,
+    max_commission_change_per_epoch: String,
+}
+
+#[derive(SimpleObject)]
+pub struct Metadata {
+    email: String,
+    description: Option<String>,
+    website: Option<String>,
+    discord_handle: Option<String>,
+    avatar: Option<String>,
+}
+
+pub struct Proposal {
+    id: u64,
+}
+
+#[Object]
+impl Proposal {
+    async fn id(&self) -> u64 {
+        self.id
+    }
+
+    async fn result(&self, ctx: &Context<'_>) -> Result<Option<ProposalTally>> {
+        match query(ctx, RPCRequestType::QueryProposalResult(self.id)).await? {
+            RPCResult::ProposalResult(result) => Ok(result.map(|result| ProposalTally {
+                result: result.result.to_string(),
+                total_voting_power: amount_string(result.total_voting_power),
+                total_yay_power: amount_string(result.total_yay_power),
+                total_nay_power: amount_string(result.total_nay_power),
+                total_abstain_power: amount_string(result.total_abstain_power),
+            })),
+            _ => Err(unexpected()),
+        }
+    }
+
+    async fn votes(&self, ctx: &Context<'_>) -> Result<Vec<ProposalVote>> {
+        match query(ctx, RPCRequestType::QueryProposalVotes(self.id)).await? {
+            RPCResult::ProposalVotes(votes) => Ok(votes
+                .into_iter()
+                .map(|vote| ProposalVote {
+                    validator: vote.validator.to_string(),
+                    delegator: vote.delegator.to_string(),
+                    vote: vote.data.to_string(),
+                })
+                .collect()),
+            _ => Err(unexpected()),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ProposalTally {
+    result: String,
+    total_voting_power: String,
+    total_yay_power: String,
+    total_nay_power: String,
+    total_abstain_power: String,
+}
+
+#[derive(SimpleObject)]
+pub struct ProposalVote {
+    validator: String,
+    delegator: String,
+    vote: String,
+}
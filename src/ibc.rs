@@ -0,0 +1,178 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use borsh::BorshDeserialize;
+use futures::future;
+use namada_sdk::error;
+use namada_sdk::ibc::core::channel::types::channel::ChannelEnd;
+use namada_sdk::ibc::core::host::types::path::CHANNEL_END_PATH_PREFIX;
+use namada_sdk::ibc::primitives::proto::Protobuf;
+use namada_sdk::ibc::storage::{ibc_denom_key_prefix, ibc_key, ibc_token, is_ibc_denom_key};
+use namada_sdk::types::address::Address;
+use namada_sdk::types::hash::Hash;
+use namada_sdk::types::storage::Key;
+use serde_json::{json, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{ErrorResponse, IbcBalancesResponse, IbcChannelsResponse, IbcDenomResponse};
+use crate::query::{balance_of, query_as, MyErrorWrapper, RPCRequestType, RPCResult};
+use crate::ServerState;
+
+async fn scan<C: RpcClient>(client: &C, prefix: Key) -> Result<Vec<(Key, Vec<u8>)>, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryStoragePrefix(prefix), |result| match result {
+        RPCResult::StoragePrefix(entries) => Some(entries),
+        _ => None,
+    })
+    .await
+}
+
+/// Every denom trace (e.g. `transfer/channel-0/uatom`) Namada has recorded for a received token
+async fn denom_traces<C: RpcClient>(client: &C) -> Result<Vec<String>, MyErrorWrapper> {
+    let mut traces = scan(client, ibc_denom_key_prefix(None))
+        .await?
+        .into_iter()
+        .filter_map(|(_, value)| String::try_from_slice(&value).ok())
+        .collect::<Vec<_>>();
+    traces.sort();
+    traces.dedup();
+    Ok(traces)
+}
+
+/// Split a trace into its `port/channel` hops and the base denom, which may itself contain `/`
+fn split_trace(trace: &str) -> (String, String) {
+    let segments = trace.split('/').collect::<Vec<_>>();
+    let mut hops = 0;
+    while segments.len() > hops * 2 + 2 && segments[hops * 2 + 1].starts_with("channel-") {
+        hops += 1;
+    }
+    (segments[..hops * 2].join("/"), segments[hops * 2..].join("/"))
+}
+
+fn trace_json(trace: &str) -> Value {
+    let (path, base_denom) = split_trace(trace);
+    json!({
+        "trace": trace,
+        "path": path,
+        "base_denom": base_denom,
+        // The `ibc/<hash>` name other chains use, and the address Namada mints the token under
+        "ibc_denom": format!("ibc/{}", Hash::sha256(trace.as_bytes())),
+        "token": ibc_token(trace),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/ibc/channels",
+    responses(
+        (status = 200, description = "All IBC channel ends with their state, counterparty and connection.", body = IbcChannelsResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_ibc_channels<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    let prefix = ibc_key(CHANNEL_END_PATH_PREFIX).map_err(|err| MyErrorWrapper(error::Error::Other(err.to_string())))?;
+    let entries = scan(&state.client, prefix).await?;
+
+    let channels = entries
+        .into_iter()
+        .filter_map(|(key, value)| {
+            // .../ports/{port}/channels/{channel}
+            let key = key.to_string();
+            let segments = key.rsplitn(4, '/').collect::<Vec<_>>();
+            let (channel_id, port_id) = (segments.first()?, segments.get(2)?);
+            let channel = ChannelEnd::decode_vec(&value).ok()?;
+            Some(json!({
+                "port_id": port_id,
+                "channel_id": channel_id,
+                "state": channel.state().to_string(),
+                "ordering": channel.ordering().to_string(),
+                "counterparty_port_id": channel.counterparty().port_id().to_string(),
+                "counterparty_channel_id": channel.counterparty().channel_id().map(|id| id.to_string()),
+                "connection_hops": channel.connection_hops().iter().map(|hop| hop.to_string()).collect::<Vec<_>>(),
+                "version": channel.version().to_string(),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({ "data": channels })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ibc/denom/{hash}",
+    params(("hash" = String, Path, description = "Hash of an ibc/<hash> denom, with or without the ibc/ prefix")),
+    responses(
+        (status = 200, description = "Resolve an IBC denom hash to its trace, base denom and Namada token address; null when unknown.", body = IbcDenomResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_ibc_denom<C: RpcClient>(State(state): State<ServerState<C>>,
+                                         Path(hash): Path<String>) -> Result<Json<Value>, MyErrorWrapper> {
+    let hash = hash.trim_start_matches("ibc/");
+    // Denoms are stored per owner as `#<ibc>/ibc_denom/{owner}/{trace hash}`, so match on the key's hash
+    // rather than decoding and hashing every recorded trace
+    let trace = scan(&state.client, ibc_denom_key_prefix(None))
+        .await?
+        .into_iter()
+        .find(|(key, _)| matches!(is_ibc_denom_key(key), Some((_, token_hash)) if token_hash.eq_ignore_ascii_case(hash)))
+        .and_then(|(_, value)| String::try_from_slice(&value).ok());
+
+    Ok(Json(json!({ "data": trace.as_deref().map(trace_json) })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ibc/balances/{owner}",
+    params(("owner" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Non-zero balances an owner holds in tokens received over IBC.", body = IbcBalancesResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_ibc_balances<C: RpcClient>(State(state): State<ServerState<C>>,
+                                            Path(owner): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let traces = denom_traces(client).await?;
+    let amounts = future::try_join_all(
+        traces.iter().map(|trace| balance_of(client, &ibc_token(trace), owner.clone())),
+    )
+    .await?;
+
+    let balances = traces
+        .iter()
+        .zip(amounts)
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(trace, amount)| {
+            let mut balance = trace_json(trace);
+            balance["amount"] = json!(amount);
+            balance
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({ "owner": owner, "data": balances })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(path: &str, base_denom: &str) -> (String, String) {
+        (path.to_string(), base_denom.to_string())
+    }
+
+    #[test]
+    fn split_trace_single_hop() {
+        assert_eq!(split_trace("transfer/channel-0/uatom"), split("transfer/channel-0", "uatom"));
+    }
+
+    #[test]
+    fn split_trace_two_hops() {
+        assert_eq!(
+            split_trace("transfer/channel-1/transfer/channel-0/uatom"),
+            split("transfer/channel-1/transfer/channel-0", "uatom")
+        );
+    }
+
+    #[test]
+    fn split_trace_keeps_slashes_in_the_base_denom() {
+        assert_eq!(split_trace("transfer/channel-0/gamm/pool/1"), split("transfer/channel-0", "gamm/pool/1"));
+    }
+}
@@ -0,0 +1,239 @@
+mod decode;
+pub mod store;
+
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use namada_sdk::error;
+use namada_sdk::state::BlockHeight;
+use namada_sdk::types::address::Address;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::client::{RpcClient, RpcQuerier};
+use crate::openapi::{
+    AccountTxsResponse, ErrorResponse, IndexStatusResponse, IndexedTransfersResponse, IndexedVotesResponse,
+};
+use crate::query::{MyErrorWrapper, Page, RPCRequestType, RPCResult};
+use crate::ServerState;
+use store::{IndexStore, IndexedBlock, TransferFilter};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexerSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_db_path")]
+    pub db_path: String,
+    // First height to index when the database is empty; later runs resume after the last indexed block
+    #[serde(default = "default_start_height")]
+    pub start_height: u64,
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+impl Default for IndexerSettings {
+    fn default() -> Self {
+        IndexerSettings {
+            enabled: false,
+            db_path: default_db_path(),
+            start_height: default_start_height(),
+            poll_interval_ms: default_poll_interval_ms(),
+        }
+    }
+}
+
+fn default_db_path() -> String {
+    "data/index.sqlite".to_string()
+}
+
+fn default_start_height() -> u64 {
+    1
+}
+
+fn default_poll_interval_ms() -> u64 {
+    2000
+}
+
+/// Follow the chain from where the index left off, one block at a time, forever.
+pub fn spawn(querier: RpcQuerier, store: IndexStore, settings: &IndexerSettings) {
+    let start_height = settings.start_height.max(1);
+    let poll_interval = Duration::from_millis(settings.poll_interval_ms);
+
+    tokio::spawn(async move {
+        let mut next_height = match blocking(&store, |store| store.last_indexed_height()).await {
+            Ok(Some(height)) => height + 1,
+            Ok(None) => start_height,
+            Err(err) => {
+                tracing::error!(error = %err, "indexer failed to read its progress, not starting");
+                return;
+            }
+        };
+        tracing::info!(next_height, "indexer started");
+
+        loop {
+            let latest_height = match querier.query(RPCRequestType::QueryLatestBlock).await {
+                Ok(RPCResult::LatestBlock(Some(block))) => block.height.0,
+                Ok(_) => 0,
+                Err(err) => {
+                    tracing::warn!(error = %err, "indexer failed to query latest block");
+                    0
+                }
+            };
+
+            while next_height <= latest_height {
+                match index_height(&querier, &store, next_height).await {
+                    Ok(()) => next_height += 1,
+                    Err(err) => {
+                        tracing::warn!(height = next_height, error = %err, "indexer failed, will retry");
+                        break;
+                    }
+                }
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    });
+}
+
+async fn index_height(querier: &RpcQuerier, store: &IndexStore, height: u64) -> Result<(), MyErrorWrapper> {
+    let block = match querier.query(RPCRequestType::QueryBlock(BlockHeight(height))).await? {
+        RPCResult::Block(block) => block,
+        _ => return Err(unexpected_result()),
+    };
+    let events = match querier.query(RPCRequestType::QueryBlockResults(BlockHeight(height))).await? {
+        RPCResult::BlockResults(_, events) => events,
+        _ => return Err(unexpected_result()),
+    };
+
+    let indexed = IndexedBlock {
+        height,
+        hash: block.hash,
+        time: block.time,
+        txs: block.txs.iter().filter_map(|bytes| decode::decode_tx(bytes)).collect(),
+        events,
+    };
+    tracing::debug!(height, txs = indexed.txs.len(), "indexed block");
+    blocking(store, move |store| store.write_block(&indexed)).await
+}
+
+fn unexpected_result() -> MyErrorWrapper {
+    MyErrorWrapper(error::Error::Other("Unexpected RPC result.".to_string()))
+}
+
+// SQLite calls block, so keep them off the async workers
+pub(crate) async fn blocking<T, F>(store: &IndexStore, f: F) -> Result<T, MyErrorWrapper>
+where
+    T: Send + 'static,
+    F: FnOnce(&IndexStore) -> rusqlite::Result<T> + Send + 'static,
+{
+    let store = store.clone();
+    tokio::task::spawn_blocking(move || f(&store))
+        .await
+        .map_err(|err| MyErrorWrapper(error::Error::Other(format!("Index query panicked: {}", err))))?
+        .map_err(|err| MyErrorWrapper(error::Error::Other(format!("Index query failed: {}", err))))
+}
+
+pub(crate) fn index_store<C>(state: &ServerState<C>) -> Result<&IndexStore, MyErrorWrapper> {
+    state.index.as_ref().ok_or_else(|| {
+        MyErrorWrapper(error::Error::Other(
+            "The indexer is disabled on this server, enable it under [indexer] in the settings.".to_string(),
+        ))
+    })
+}
+
+#[derive(Deserialize)]
+pub struct TransferParams {
+    source: Option<Address>,
+    target: Option<Address>,
+    token: Option<Address>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/index/status",
+    responses(
+        (status = 200, description = "Height of the last block the indexer has written, null before the first one.", body = IndexStatusResponse),
+        (status = 500, description = "Indexer disabled or index query error", body = ErrorResponse),
+    )
+)]
+pub async fn get_index_status<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    let store = index_store(&state)?;
+    let last_indexed_height = blocking(store, |store| store.last_indexed_height()).await?;
+    Ok(Json(json!({ "last_indexed_height": last_indexed_height })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/index/transfers",
+    params(
+        ("source" = Option<String>, Query, description = "Only transfers from this address"),
+        ("target" = Option<String>, Query, description = "Only transfers to this address"),
+        ("token" = Option<String>, Query, description = "Only transfers of this token"),
+        ("limit" = Option<u64>, Query, description = "Page size, 50 by default and at most 500"),
+        ("offset" = Option<u64>, Query, description = "Transfers to skip"),
+    ),
+    responses(
+        (status = 200, description = "Indexed transparent transfers matching the filters, newest first.", body = IndexedTransfersResponse),
+        (status = 500, description = "Indexer disabled or index query error", body = ErrorResponse),
+    )
+)]
+pub async fn get_transfers<C: RpcClient>(State(state): State<ServerState<C>>,
+                                         Query(params): Query<TransferParams>,
+                                         Query(page): Query<Page>) -> Result<Json<Value>, MyErrorWrapper> {
+    let store = index_store(&state)?;
+    let filter = TransferFilter {
+        source: params.source.map(|address| address.to_string()),
+        target: params.target.map(|address| address.to_string()),
+        token: params.token.map(|address| address.to_string()),
+    };
+    let (limit, offset) = (page.limit(), page.offset());
+    let transfers = blocking(store, move |store| store.transfers(&filter, limit, offset)).await?;
+    Ok(Json(json!({ "data": transfers, "limit": limit, "offset": offset })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/index/votes/{address}",
+    params(
+        ("address" = String, Path, description = "Voter address, e.g. tnam1q..."),
+        ("limit" = Option<u64>, Query, description = "Page size, 50 by default and at most 500"),
+        ("offset" = Option<u64>, Query, description = "Votes to skip"),
+    ),
+    responses(
+        (status = 200, description = "Votes an address has cast across all proposals, newest first.", body = IndexedVotesResponse),
+        (status = 500, description = "Indexer disabled or index query error", body = ErrorResponse),
+    )
+)]
+pub async fn get_votes<C: RpcClient>(State(state): State<ServerState<C>>,
+                                     Path(voter): Path<Address>,
+                                     Query(page): Query<Page>) -> Result<Json<Value>, MyErrorWrapper> {
+    let store = index_store(&state)?;
+    let voter = voter.to_string();
+    let (limit, offset) = (page.limit(), page.offset());
+    let votes = blocking(store, move |store| store.votes_by(&voter, limit, offset)).await?;
+    Ok(Json(json!({ "data": votes, "limit": limit, "offset": offset })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/account/{address}/txs",
+    params(
+        ("address" = String, Path, description = "Account address, e.g. tnam1q..."),
+        ("limit" = Option<u64>, Query, description = "Page size, 50 by default and at most 500"),
+        ("offset" = Option<u64>, Query, description = "Txs to skip"),
+    ),
+    responses(
+        (status = 200, description = "Indexed txs the address took part in as signer, fee payer, source, target, delegator, validator or voter, newest first.", body = AccountTxsResponse),
+        (status = 500, description = "Indexer disabled or index query error", body = ErrorResponse),
+    )
+)]
+pub async fn get_account_txs<C: RpcClient>(State(state): State<ServerState<C>>,
+                                           Path(address): Path<Address>,
+                                           Query(page): Query<Page>) -> Result<Json<Value>, MyErrorWrapper> {
+    let store = index_store(&state)?;
+    let address = address.to_string();
+    let (limit, offset) = (page.limit(), page.offset());
+    let txs = blocking(store, move |store| store.txs_by_address(&address, limit, offset)).await?;
+    Ok(Json(json!({ "data": txs, "limit": limit, "offset": offset })))
+}
@@ -0,0 +1,134 @@
+use borsh::BorshDeserialize;
+use namada_sdk::governance::{InitProposalData, VoteProposalData};
+use namada_sdk::tx::data::pos::{Bond, Redelegation, Unbond, Withdraw};
+use namada_sdk::tx::data::TxType;
+use namada_sdk::tx::{Section, Signer, Tx};
+use namada_sdk::types::address::Address;
+use namada_sdk::types::token::Transfer;
+
+/// What the indexer keeps from one block tx. Wrapper and decrypted copies of the same tx
+/// decode to the same `hash`, which is also the `hash` attribute of its applied event.
+pub struct DecodedTx {
+    pub hash: String,
+    pub is_wrapper: bool,
+    // Wasm name from the code section tag (e.g. "tx_transfer"), or the code hash when untagged
+    pub kind: String,
+    pub addresses: Vec<(Address, &'static str)>,
+    pub transfer: Option<TransferRecord>,
+    pub vote: Option<VoteRecord>,
+}
+
+pub struct TransferRecord {
+    pub source: Address,
+    pub target: Address,
+    pub token: Address,
+    pub amount: String,
+}
+
+pub struct VoteRecord {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub vote: String,
+}
+
+pub fn decode_tx(bytes: &[u8]) -> Option<DecodedTx> {
+    let tx = Tx::try_from(bytes).ok()?;
+    let is_wrapper = match tx.header.tx_type {
+        TxType::Wrapper(_) => true,
+        TxType::Decrypted(_) => false,
+        // Protocol txs are validator bookkeeping, raw txs never make it into a block
+        _ => return None,
+    };
+
+    let mut addresses = Vec::new();
+    if let TxType::Wrapper(wrapper) = &tx.header.tx_type {
+        addresses.push((wrapper.fee_payer(), "fee_payer"));
+    }
+    for section in &tx.sections {
+        if let Section::Signature(signature) = section {
+            match &signature.signer {
+                Signer::Address(address) => addresses.push((address.clone(), "signer")),
+                Signer::PubKeys(keys) => addresses.extend(keys.iter().map(|key| (Address::from(key), "signer"))),
+            }
+        }
+    }
+
+    let tag = match tx.get_section(tx.code_sechash()).as_deref() {
+        Some(Section::Code(code)) => code.tag.clone(),
+        _ => None,
+    };
+    let kind = match tag {
+        Some(tag) => tag.trim_end_matches(".wasm").to_string(),
+        None => tx.code_sechash().to_string(),
+    };
+
+    let data = tx.data().unwrap_or_default();
+    let mut transfer = None;
+    let mut vote = None;
+    match kind.as_str() {
+        "tx_transfer" => {
+            if let Ok(data) = Transfer::try_from_slice(&data) {
+                addresses.push((data.source.clone(), "source"));
+                addresses.push((data.target.clone(), "target"));
+                transfer = Some(TransferRecord {
+                    source: data.source,
+                    target: data.target,
+                    token: data.token,
+                    amount: data.amount.to_string(),
+                });
+            }
+        }
+        "tx_bond" => {
+            if let Ok(data) = Bond::try_from_slice(&data) {
+                addresses.push((data.source.unwrap_or_else(|| data.validator.clone()), "delegator"));
+                addresses.push((data.validator, "validator"));
+            }
+        }
+        "tx_unbond" => {
+            if let Ok(data) = Unbond::try_from_slice(&data) {
+                addresses.push((data.source.unwrap_or_else(|| data.validator.clone()), "delegator"));
+                addresses.push((data.validator, "validator"));
+            }
+        }
+        "tx_withdraw" | "tx_claim_rewards" => {
+            if let Ok(data) = Withdraw::try_from_slice(&data) {
+                addresses.push((data.source.unwrap_or_else(|| data.validator.clone()), "delegator"));
+                addresses.push((data.validator, "validator"));
+            }
+        }
+        "tx_redelegate" => {
+            if let Ok(data) = Redelegation::try_from_slice(&data) {
+                addresses.push((data.owner, "delegator"));
+                addresses.push((data.src_validator, "validator"));
+                addresses.push((data.dest_validator, "validator"));
+            }
+        }
+        "tx_vote_proposal" => {
+            if let Ok(data) = VoteProposalData::try_from_slice(&data) {
+                addresses.push((data.voter.clone(), "voter"));
+                vote = Some(VoteRecord {
+                    proposal_id: data.id,
+                    voter: data.voter,
+                    vote: data.vote.to_string(),
+                });
+            }
+        }
+        "tx_init_proposal" => {
+            if let Ok(data) = InitProposalData::try_from_slice(&data) {
+                addresses.push((data.author, "author"));
+            }
+        }
+        _ => {}
+    }
+    addresses.sort_by(|a, b| (a.0.to_string(), a.1).cmp(&(b.0.to_string(), b.1)));
+    addresses.dedup();
+
+    Some(DecodedTx {
+        hash: tx.update_header(TxType::Raw).header_hash().to_string(),
+        is_wrapper,
+        kind,
+        addresses,
+        transfer,
+        vote,
+    })
+}
@@ -0,0 +1,451 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::indexer::decode::DecodedTx;
+use crate::query::EventSerializable;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    height INTEGER PRIMARY KEY,
+    hash TEXT NOT NULL,
+    time TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS txs (
+    hash TEXT PRIMARY KEY,
+    height INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL,
+    code TEXT,
+    gas_used TEXT,
+    applied_height INTEGER
+);
+CREATE TABLE IF NOT EXISTS tx_addresses (
+    tx_hash TEXT NOT NULL,
+    address TEXT NOT NULL,
+    role TEXT NOT NULL,
+    height INTEGER NOT NULL,
+    PRIMARY KEY (tx_hash, address, role)
+);
+CREATE INDEX IF NOT EXISTS tx_addresses_by_address ON tx_addresses (address, height);
+CREATE TABLE IF NOT EXISTS transfers (
+    tx_hash TEXT PRIMARY KEY,
+    height INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    target TEXT NOT NULL,
+    token TEXT NOT NULL,
+    amount TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transfers_by_source ON transfers (source, height);
+CREATE INDEX IF NOT EXISTS transfers_by_target ON transfers (target, height);
+CREATE TABLE IF NOT EXISTS votes (
+    tx_hash TEXT PRIMARY KEY,
+    height INTEGER NOT NULL,
+    proposal_id INTEGER NOT NULL,
+    voter TEXT NOT NULL,
+    vote TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS votes_by_voter ON votes (voter, height);
+CREATE TABLE IF NOT EXISTS events (
+    height INTEGER NOT NULL,
+    idx INTEGER NOT NULL,
+    event_type TEXT NOT NULL,
+    tx_hash TEXT,
+    attributes TEXT NOT NULL,
+    PRIMARY KEY (height, idx)
+);
+";
+
+pub struct IndexedBlock {
+    pub height: u64,
+    pub hash: String,
+    pub time: String,
+    pub txs: Vec<DecodedTx>,
+    pub events: Vec<EventSerializable>,
+}
+
+#[derive(Serialize)]
+pub struct TransferRow {
+    pub tx_hash: String,
+    pub height: u64,
+    pub source: String,
+    pub target: String,
+    pub token: String,
+    pub amount: String,
+    pub status: String,
+}
+
+#[derive(Serialize)]
+pub struct VoteRow {
+    pub tx_hash: String,
+    pub height: u64,
+    pub proposal_id: u64,
+    pub voter: String,
+    pub vote: String,
+    pub status: String,
+}
+
+#[derive(Serialize)]
+pub struct AccountTxRow {
+    pub hash: String,
+    pub height: u64,
+    pub kind: String,
+    pub status: String,
+    pub code: Option<String>,
+    pub applied_height: Option<u64>,
+    pub roles: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct TransferFilter {
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub token: Option<String>,
+}
+
+/// SQLite-backed index. The connection sits behind a mutex, so call it from
+/// `spawn_blocking` rather than directly on the async runtime.
+#[derive(Clone)]
+pub struct IndexStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl IndexStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(IndexStore { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // A panic while holding the lock can't leave SQLite itself inconsistent
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn last_indexed_height(&self) -> rusqlite::Result<Option<u64>> {
+        self.conn()
+            .query_row("SELECT MAX(height) FROM blocks", [], |row| row.get::<_, Option<u64>>(0))
+    }
+
+    pub fn write_block(&self, block: &IndexedBlock) -> rusqlite::Result<()> {
+        let mut conn = self.conn();
+        let db_tx = conn.transaction()?;
+
+        for tx in &block.txs {
+            // The wrapper lands first and carries everything we index; its decrypted copy in a
+            // later block only confirms it, through the applied event handled below
+            let status = if tx.is_wrapper { "accepted" } else { "pending" };
+            db_tx.execute(
+                "INSERT OR IGNORE INTO txs (hash, height, kind, status) VALUES (?1, ?2, ?3, ?4)",
+                params![tx.hash, block.height, tx.kind, status],
+            )?;
+            for (address, role) in &tx.addresses {
+                db_tx.execute(
+                    "INSERT OR IGNORE INTO tx_addresses (tx_hash, address, role, height) VALUES (?1, ?2, ?3, ?4)",
+                    params![tx.hash, address.to_string(), role, block.height],
+                )?;
+            }
+            if let Some(transfer) = &tx.transfer {
+                db_tx.execute(
+                    "INSERT OR IGNORE INTO transfers (tx_hash, height, source, target, token, amount) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        tx.hash,
+                        block.height,
+                        transfer.source.to_string(),
+                        transfer.target.to_string(),
+                        transfer.token.to_string(),
+                        transfer.amount,
+                    ],
+                )?;
+            }
+            if let Some(vote) = &tx.vote {
+                db_tx.execute(
+                    "INSERT OR IGNORE INTO votes (tx_hash, height, proposal_id, voter, vote) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![tx.hash, block.height, vote.proposal_id, vote.voter.to_string(), vote.vote],
+                )?;
+            }
+        }
+
+        for (idx, event) in block.events.iter().enumerate() {
+            let tx_hash = event.attributes.get("hash").map(|hash| hash.to_uppercase());
+            db_tx.execute(
+                "INSERT OR REPLACE INTO events (height, idx, event_type, tx_hash, attributes) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    block.height,
+                    idx as u64,
+                    event.event_type,
+                    tx_hash,
+                    serde_json::to_string(&event.attributes).unwrap_or_default(),
+                ],
+            )?;
+            if let Some(tx_hash) = tx_hash.as_ref().filter(|_| event.event_type == "applied") {
+                let code = event.attributes.get("code");
+                let status = if code.map(String::as_str) == Some("0") { "applied" } else { "failed" };
+                db_tx.execute(
+                    "UPDATE txs SET status = ?1, code = ?2, gas_used = ?3, applied_height = ?4 WHERE hash = ?5",
+                    params![status, code, event.attributes.get("gas_used"), block.height, tx_hash],
+                )?;
+            }
+        }
+
+        db_tx.execute(
+            "INSERT OR REPLACE INTO blocks (height, hash, time) VALUES (?1, ?2, ?3)",
+            params![block.height, block.hash, block.time],
+        )?;
+        db_tx.commit()
+    }
+
+    pub fn transfers(&self, filter: &TransferFilter, limit: u64, offset: u64) -> rusqlite::Result<Vec<TransferRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT t.tx_hash, t.height, t.source, t.target, t.token, t.amount, x.status \
+             FROM transfers t JOIN txs x ON x.hash = t.tx_hash \
+             WHERE (?1 IS NULL OR t.source = ?1) AND (?2 IS NULL OR t.target = ?2) AND (?3 IS NULL OR t.token = ?3) \
+             ORDER BY t.height DESC, t.tx_hash LIMIT ?4 OFFSET ?5",
+        )?;
+        let rows = stmt.query_map(
+            params![filter.source, filter.target, filter.token, limit, offset],
+            |row| {
+                Ok(TransferRow {
+                    tx_hash: row.get(0)?,
+                    height: row.get(1)?,
+                    source: row.get(2)?,
+                    target: row.get(3)?,
+                    token: row.get(4)?,
+                    amount: row.get(5)?,
+                    status: row.get(6)?,
+                })
+            },
+        )?;
+        rows.collect()
+    }
+
+    /// Txs touching `address` in any role, newest first, with one row per tx
+    pub fn txs_by_address(&self, address: &str, limit: u64, offset: u64) -> rusqlite::Result<Vec<AccountTxRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT x.hash, x.height, x.kind, x.status, x.code, x.applied_height, GROUP_CONCAT(a.role) \
+             FROM tx_addresses a JOIN txs x ON x.hash = a.tx_hash \
+             WHERE a.address = ?1 GROUP BY x.hash \
+             ORDER BY x.height DESC, x.hash LIMIT ?2 OFFSET ?3",
+        )?;
+        let rows = stmt.query_map(params![address, limit, offset], |row| {
+            let roles: String = row.get(6)?;
+            Ok(AccountTxRow {
+                hash: row.get(0)?,
+                height: row.get(1)?,
+                kind: row.get(2)?,
+                status: row.get(3)?,
+                code: row.get(4)?,
+                applied_height: row.get(5)?,
+                roles: roles.split(',').map(str::to_string).collect(),
+            })
+        })?;
+        rows.collect()
+    }
+
+    pub fn votes_by(&self, voter: &str, limit: u64, offset: u64) -> rusqlite::Result<Vec<VoteRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT v.tx_hash, v.height, v.proposal_id, v.voter, v.vote, x.status \
+             FROM votes v JOIN txs x ON x.hash = v.tx_hash \
+             WHERE v.voter = ?1 ORDER BY v.height DESC, v.tx_hash LIMIT ?2 OFFSET ?3",
+        )?;
+        let rows = stmt.query_map(params![voter, limit, offset], |row| {
+            Ok(VoteRow {
+                tx_hash: row.get(0)?,
+                height: row.get(1)?,
+                proposal_id: row.get(2)?,
+                voter: row.get(3)?,
+                vote: row.get(4)?,
+                status: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use namada_sdk::types::address::Address;
+
+    use super::*;
+    use crate::indexer::decode::{TransferRecord, VoteRecord};
+
+    const NAAN: &str = "tnam1qxvg64psvhwumv3mwrrjfcz0h3t3274hwggyzcee";
+    const ALICE: &str = "tnam1q8c5j8gjaw7yz2fllzputv2cfn9jjktlzgyezv44";
+    const BOB: &str = "tnam1qpg3k3rfe2qjr74l53j4c0naa8x34cza4gl78htw";
+
+    fn address(encoded: &str) -> Address {
+        Address::decode(encoded).unwrap()
+    }
+
+    fn tx(hash: &str, is_wrapper: bool, addresses: Vec<(&str, &'static str)>) -> DecodedTx {
+        DecodedTx {
+            hash: hash.to_string(),
+            is_wrapper,
+            kind: "tx_transfer".to_string(),
+            addresses: addresses.into_iter().map(|(encoded, role)| (address(encoded), role)).collect(),
+            transfer: None,
+            vote: None,
+        }
+    }
+
+    fn transfer(hash: &str, source: &str, target: &str) -> DecodedTx {
+        let mut tx = tx(hash, true, vec![(source, "source"), (target, "target")]);
+        tx.transfer = Some(TransferRecord {
+            source: address(source),
+            target: address(target),
+            token: address(NAAN),
+            amount: "100".to_string(),
+        });
+        tx
+    }
+
+    fn vote(hash: &str, proposal_id: u64, voter: &str) -> DecodedTx {
+        let mut tx = tx(hash, true, vec![(voter, "voter")]);
+        tx.kind = "tx_vote_proposal".to_string();
+        tx.vote = Some(VoteRecord { proposal_id, voter: address(voter), vote: "yay".to_string() });
+        tx
+    }
+
+    fn applied(hash: &str, code: &str) -> EventSerializable {
+        EventSerializable {
+            event_type: "applied".to_string(),
+            level: "Block".to_string(),
+            attributes: HashMap::from([
+                // Events carry the hash in lowercase, the decoded txs in uppercase
+                ("hash".to_string(), hash.to_lowercase()),
+                ("code".to_string(), code.to_string()),
+                ("gas_used".to_string(), "7263".to_string()),
+            ]),
+        }
+    }
+
+    fn block(height: u64, txs: Vec<DecodedTx>, events: Vec<EventSerializable>) -> IndexedBlock {
+        IndexedBlock {
+            height,
+            hash: format!("BLOCK{}", height),
+            time: "2024-01-01T00:00:00Z".to_string(),
+            txs,
+            events,
+        }
+    }
+
+    fn store() -> IndexStore {
+        IndexStore::open(":memory:").unwrap()
+    }
+
+    #[test]
+    fn wrapper_is_accepted_then_applied_or_failed_by_its_event() {
+        let store = store();
+        let wrappers = vec![tx("AA", true, vec![(ALICE, "signer")]), tx("BB", true, vec![(ALICE, "signer")])];
+        store.write_block(&block(10, wrappers, vec![])).unwrap();
+        let statuses = |store: &IndexStore| {
+            store
+                .txs_by_address(ALICE, 50, 0)
+                .unwrap()
+                .into_iter()
+                .map(|row| (row.hash, row.status, row.code, row.applied_height))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(statuses(&store), vec![
+            ("AA".to_string(), "accepted".to_string(), None, None),
+            ("BB".to_string(), "accepted".to_string(), None, None),
+        ]);
+
+        let decrypted = vec![tx("AA", false, vec![]), tx("BB", false, vec![])];
+        store.write_block(&block(11, decrypted, vec![applied("AA", "0"), applied("BB", "1")])).unwrap();
+        assert_eq!(statuses(&store), vec![
+            ("AA".to_string(), "applied".to_string(), Some("0".to_string()), Some(11)),
+            ("BB".to_string(), "failed".to_string(), Some("1".to_string()), Some(11)),
+        ]);
+        assert_eq!(store.last_indexed_height().unwrap(), Some(11));
+    }
+
+    #[test]
+    fn decrypted_copy_does_not_duplicate_the_wrapper() {
+        let store = store();
+        store.write_block(&block(10, vec![transfer("AA", ALICE, BOB)], vec![])).unwrap();
+        let mut decrypted = transfer("AA", ALICE, BOB);
+        decrypted.is_wrapper = false;
+        store.write_block(&block(11, vec![decrypted], vec![])).unwrap();
+
+        let txs = store.txs_by_address(ALICE, 50, 0).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!((txs[0].height, txs[0].status.as_str()), (10, "accepted"));
+        assert_eq!(txs[0].roles, vec!["source"]);
+        let transfers = store.transfers(&TransferFilter::default(), 50, 0).unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].height, 10);
+    }
+
+    #[test]
+    fn transfers_filter_and_page_newest_first() {
+        let store = store();
+        store.write_block(&block(10, vec![transfer("AA", ALICE, BOB)], vec![])).unwrap();
+        store.write_block(&block(11, vec![transfer("BB", BOB, ALICE)], vec![])).unwrap();
+        store.write_block(&block(12, vec![transfer("CC", ALICE, BOB)], vec![])).unwrap();
+        let hashes = |filter: TransferFilter, limit, offset| {
+            store
+                .transfers(&filter, limit, offset)
+                .unwrap()
+                .into_iter()
+                .map(|row| row.tx_hash)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(hashes(TransferFilter::default(), 50, 0), vec!["CC", "BB", "AA"]);
+        let from_alice = TransferFilter { source: Some(ALICE.to_string()), ..Default::default() };
+        assert_eq!(hashes(from_alice, 50, 0), vec!["CC", "AA"]);
+        let to_alice = TransferFilter { target: Some(ALICE.to_string()), ..Default::default() };
+        assert_eq!(hashes(to_alice, 50, 0), vec!["BB"]);
+        let in_naan = TransferFilter { token: Some(NAAN.to_string()), ..Default::default() };
+        assert_eq!(hashes(in_naan, 50, 0).len(), 3);
+        let in_other_token = TransferFilter { token: Some(ALICE.to_string()), ..Default::default() };
+        assert!(hashes(in_other_token, 50, 0).is_empty());
+        assert_eq!(hashes(TransferFilter::default(), 1, 1), vec!["BB"]);
+        assert!(hashes(TransferFilter::default(), 50, 3).is_empty());
+    }
+
+    #[test]
+    fn votes_by_voter_are_paged_newest_first() {
+        let store = store();
+        store.write_block(&block(10, vec![vote("AA", 1, ALICE), vote("BB", 1, BOB)], vec![])).unwrap();
+        store.write_block(&block(11, vec![vote("CC", 2, ALICE)], vec![applied("CC", "0")])).unwrap();
+
+        let votes = store.votes_by(ALICE, 50, 0).unwrap();
+        assert_eq!(
+            votes.iter().map(|row| (row.tx_hash.as_str(), row.proposal_id, row.status.as_str())).collect::<Vec<_>>(),
+            vec![("CC", 2, "applied"), ("AA", 1, "accepted")],
+        );
+        let page = store.votes_by(ALICE, 1, 1).unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].tx_hash, "AA");
+    }
+
+    #[test]
+    fn txs_by_address_has_one_row_per_tx_with_all_roles() {
+        let store = store();
+        let roles = vec![(ALICE, "fee_payer"), (ALICE, "signer"), (ALICE, "source"), (BOB, "target")];
+        store.write_block(&block(10, vec![tx("AA", true, roles)], vec![])).unwrap();
+        store.write_block(&block(11, vec![tx("BB", true, vec![(BOB, "signer"), (ALICE, "target")])], vec![])).unwrap();
+        store.write_block(&block(12, vec![tx("CC", true, vec![(BOB, "signer")])], vec![])).unwrap();
+
+        let txs = store.txs_by_address(ALICE, 50, 0).unwrap();
+        assert_eq!(txs.iter().map(|row| row.hash.as_str()).collect::<Vec<_>>(), vec!["BB", "AA"]);
+        assert_eq!(txs[0].roles, vec!["target"]);
+        let mut roles = txs[1].roles.clone();
+        roles.sort();
+        assert_eq!(roles, vec!["fee_payer", "signer", "source"]);
+
+        let page = store.txs_by_address(ALICE, 1, 1).unwrap();
+        assert_eq!(page.iter().map(|row| row.hash.as_str()).collect::<Vec<_>>(), vec!["AA"]);
+    }
+}
@@ -0,0 +1,243 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    routing::{get, post},
+    Extension, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use client::{RpcClient, RpcQuerier};
+use indexer::store::IndexStore;
+use indexer::IndexerSettings;
+use shielded::ShieldedSettings;
+use query::{get_epoch, get_proposals};
+use tendermint_rpc::{self, HttpClient};
+use tokio::sync::watch;
+use tower_http::cors::{CorsLayer, Any};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+pub mod account;
+pub mod batch;
+pub mod client;
+pub mod eth_bridge;
+pub mod fee;
+pub mod graphql;
+pub mod ibc;
+pub mod indexer;
+pub mod masp;
+pub mod openapi;
+pub mod parameters;
+pub mod query;
+pub mod shielded;
+pub mod sse;
+pub mod staking;
+pub mod supply;
+pub mod validator;
+pub mod ws;
+
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Settings {
+    pub rpc_url: String,
+    pub bind_ip: String,
+    pub port: u16,
+    // How long in-flight requests may keep running after SIGTERM/SIGINT before we force exit
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+    // Filter directives understood by `tracing_subscriber::EnvFilter`, e.g. "info" or "namada_rest=debug"
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    // "pretty" or "json"
+    #[serde(default = "default_log_format")]
+    pub log_format: String,
+    // Upper bound on the number of queries accepted by one `POST /batch`
+    #[serde(default = "default_batch_max_queries")]
+    pub batch_max_queries: usize,
+    // How many queries of a batch run against the node at the same time
+    #[serde(default = "default_batch_concurrency")]
+    pub batch_concurrency: usize,
+    // Deepest field nesting one GraphQL query may use
+    #[serde(default = "default_graphql_max_depth")]
+    pub graphql_max_depth: usize,
+    // Upper bound on the number of fields one GraphQL query may select, aliases included
+    #[serde(default = "default_graphql_max_complexity")]
+    pub graphql_max_complexity: usize,
+    // How often the `/ws` feed polls the node for new blocks and epochs
+    #[serde(default = "default_ws_poll_interval_ms")]
+    pub ws_poll_interval_ms: u64,
+    // Longest a `/tx_event/:tx_hash/stream` client may wait for its tx to be applied
+    #[serde(default = "default_tx_stream_timeout_secs")]
+    pub tx_stream_timeout_secs: u64,
+    #[serde(default = "default_tx_stream_poll_interval_ms")]
+    pub tx_stream_poll_interval_ms: u64,
+    #[serde(default)]
+    pub indexer: IndexerSettings,
+    #[serde(default)]
+    pub shielded: ShieldedSettings,
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_format() -> String {
+    "pretty".to_string()
+}
+
+fn default_batch_max_queries() -> usize {
+    50
+}
+
+fn default_batch_concurrency() -> usize {
+    8
+}
+
+fn default_graphql_max_depth() -> usize {
+    8
+}
+
+fn default_graphql_max_complexity() -> usize {
+    200
+}
+
+fn default_ws_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_tx_stream_timeout_secs() -> u64 {
+    120
+}
+
+fn default_tx_stream_poll_interval_ms() -> u64 {
+    1000
+}
+
+#[derive(Clone)]
+pub struct ServerState<C = HttpClient> {
+    client: C,
+    config: Settings,
+    index: Option<IndexStore>,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl<C: RpcClient> ServerState<C> {
+    pub fn new(client: C, config: Settings) -> Self {
+        // Never flips unless `with_shutdown` wires up a real sender
+        let (_, shutdown) = watch::channel(false);
+        ServerState { client, config, index: None, shutdown }
+    }
+
+    /// Serve the indexed routes from `index`
+    pub fn with_index(mut self, index: IndexStore) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// End long-lived responses (the tx event streams) once `shutdown` turns true, so they
+    /// don't hold the graceful shutdown open until the drain deadline
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+}
+
+pub fn router<C: RpcClient>(state: ServerState<C>) -> Router {
+    let schema = graphql::build_schema(state.client.clone(), &state.config);
+    let chain_events = Arc::new(ws::ChainEvents::new(
+        RpcQuerier::new(state.client.clone()),
+        Duration::from_millis(state.config.ws_poll_interval_ms),
+    ));
+
+    Router::new()
+        .route("/", get(|| async { "Namada REST API Running" }))
+        .route("/proposal_result/:id", get(get_proposals::<C>))
+        .route("/epoch", get(get_epoch::<C>))
+        .route("/epoch_at_height/:height", get(query::get_epoch_at_height::<C>))
+        .route("/balance/:wallet",get(query::get_balance::<C>))
+        .route("/validator_state/:address/:epoch",get(query::get_validator_state::<C>))
+        .route("/delegator_delegation/:wallet",get(query::get_delegators_delegation::<C>))
+        .route("/delegator_delegation_at/:wallet/:epoch",get(query::get_delegators_delegation_at::<C>))
+        .route("/metadata/:address/:epoch",get(query::get_meta_data::<C>))
+        .route("/governance", get(query::get_governance_parameters::<C>))
+        .route("/pos_params", get(query::get_pos_parameters::<C>))
+        .route("/parameters", get(parameters::get_parameters::<C>))
+        .route("/proposal_votes/:id", get(query::get_proposal_votes::<C>))
+        .route("/is_steward/:wallet",get(query::check_steward::<C>))
+        .route("/validator_consensus_keys/:wallet",get(query::get_validator_consensus_keys::<C>))
+        .route("/tx_event/:tx_hash",get(query::get_tx_events::<C>))
+        .route("/tx_event/:tx_hash/stream",get(sse::stream_tx_event::<C>))
+        .route("/native_token",get(query::get_native_token::<C>))
+        .route("/query_block",get(query::get_latest_block::<C>))
+        .route("/is_validator/:wallet",get(query::check_is_validator::<C>))
+        .route("/is_delegator/:wallet",get(query::check_is_delegator::<C>))
+        .route("/masp_reward",get(query::get_masp_reward::<C>))
+        .route("/total_staked/:epoch",get(query::get_total_staked_tokens::<C>))
+        .route("/validator_stake/:address/:epoch",get(query::get_validator_stake::<C>))
+        .route("/validator/:address", get(validator::get_validator::<C>))
+        .route("/validator/:address/delegators", get(validator::get_validator_delegators::<C>))
+        .route("/validator/:address/liveness", get(validator::get_validator_liveness::<C>))
+        .route("/validator/:address/commission", get(validator::get_validator_commission::<C>))
+        .route("/staking/apr", get(staking::get_staking_apr::<C>))
+        .route("/staking/apr/:address", get(staking::get_validator_apr::<C>))
+        .route("/supply/:token", get(supply::get_supply::<C>))
+        .route("/masp/conversions", get(masp::get_masp_conversions::<C>))
+        .route("/masp/rewards", get(masp::get_masp_rewards::<C>))
+        .route("/shielded/balance", post(shielded::post_shielded_balance::<C>))
+        .route("/ibc/channels", get(ibc::get_ibc_channels::<C>))
+        .route("/ibc/denom/:hash", get(ibc::get_ibc_denom::<C>))
+        .route("/ibc/balances/:owner", get(ibc::get_ibc_balances::<C>))
+        .route("/eth_bridge/pool", get(eth_bridge::get_bridge_pool::<C>))
+        .route("/eth_bridge/contracts", get(eth_bridge::get_bridge_contracts::<C>))
+        .route("/eth_bridge/whitelist", get(eth_bridge::get_bridge_whitelist::<C>))
+        .route("/index/status", get(indexer::get_index_status::<C>))
+        .route("/index/transfers", get(indexer::get_transfers::<C>))
+        .route("/index/votes/:address", get(indexer::get_votes::<C>))
+        .route("/account/:address", get(account::get_account::<C>))
+        .route("/account/:address/keys", get(account::get_account_keys::<C>))
+        .route("/account/:address/txs", get(indexer::get_account_txs::<C>))
+        .route("/tx/estimate_fee", post(fee::post_estimate_fee::<C>))
+        .route("/batch", post(batch::post_batch::<C>))
+        .route("/graphql", get(graphql::graphiql).post(graphql::graphql_handler))
+        .route("/ws", get(ws::ws_handler))
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", openapi::ApiDoc::openapi()))
+        .with_state(state)
+        .layer(Extension(schema))
+        .layer(Extension(chain_events))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(
+            TraceLayer::new_for_http().make_span_with(|request: &Request| {
+                let request_id = request
+                    .headers()
+                    .get("x-request-id")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+                let route = request
+                    .extensions()
+                    .get::<MatchedPath>()
+                    .map(MatchedPath::as_str)
+                    .unwrap_or_default();
+                tracing::info_span!(
+                    "request",
+                    request_id,
+                    method = %request.method(),
+                    route,
+                    path = %request.uri().path(),
+                )
+            }),
+        )
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .layer(
+            CorsLayer::new()
+                .allow_methods(Any)
+                .allow_origin(Any)
+                .allow_headers(Any)
+                .allow_credentials(false),
+        )
+}
@@ -1,30 +1,15 @@
-use axum::{
-    routing::get,
-    Router,
-};
-use serde::{Deserialize, Serialize};
+use namada_rest::client::RpcQuerier;
+use namada_rest::indexer::{self, store::IndexStore};
+use namada_rest::{router, ServerState, Settings};
+use std::future::IntoFuture;
 use std::process;
 use std::{fs};
 use std::path::Path;
-use query::{get_epoch, get_proposals};
+use std::sync::Arc;
+use std::time::Duration;
 use tendermint_rpc::{self, HttpClient};
-use tower_http::cors::{CorsLayer, Any};
-
-mod query;
-
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct Settings {
-    rpc_url: String,
-    bind_ip: String,
-    port: u16,
-}
-
-#[derive(Clone)]
-pub struct ServerState {
-    client: HttpClient,
-    config: Settings
-}
+use tokio::sync::{watch, Notify};
+use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
 async fn main() {
@@ -36,48 +21,98 @@ async fn main() {
         process::exit(1);
     });
 
+    init_tracing(&config);
+
     // Connect to RPC
     let client = HttpClient::new(config.rpc_url.as_str()).unwrap();
 
-    // build our application with a single route
-    let app = Router::new()
-        .route("/", get(|| async { "Namada REST API Running" }))
-        .route("/proposal_result/:id", get(get_proposals))
-        .route("/epoch", get(get_epoch))
-        .route("/epoch_at_height/:height", get(query::get_epoch_at_height))
-        .route("/balance/:wallet",get(query::get_balance))
-        .route("/validator_state/:address/:epoch",get(query::get_validator_state))
-        .route("/delegator_delegation/:wallet",get(query::get_delegators_delegation))
-        .route("/delegator_delegation_at/:wallet/:epoch",get(query::get_delegators_delegation_at))
-        .route("/metadata/:address/:epoch",get(query::get_meta_data))
-        .route("/governance", get(query::get_governance_parameters))
-        .route("/pos_params", get(query::get_pos_parameters))
-        .route("/proposal_votes/:id", get(query::get_proposal_votes))
-        .route("/is_steward/:wallet",get(query::check_steward))
-        .route("/validator_consensus_keys/:wallet",get(query::get_validator_consensus_keys))
-        .route("/tx_event/:tx_hash",get(query::get_tx_events))
-        .route("/native_token",get(query::get_native_token))
-        .route("/query_block",get(query::get_latest_block))
-        .route("/is_validator/:wallet",get(query::check_is_validator))
-        .route("/is_delegator/:wallet",get(query::check_is_delegator))
-        .route("/masp_reward",get(query::get_masp_reward))
-        .route("/total_staked/:epoch",get(query::get_total_staked_tokens))
-        .route("/validator_stake/:address/:epoch",get(query::get_validator_stake))
-        .with_state(ServerState { client, config: config.clone() })
-        .layer(
-            CorsLayer::new()
-                .allow_methods(Any)
-                .allow_origin(Any)
-                .allow_headers(Any)
-                .allow_credentials(false),
-        );
+    let (stop_streams, streams_stopped) = watch::channel(false);
+    let mut state = ServerState::new(client.clone(), config.clone()).with_shutdown(streams_stopped);
+    if config.indexer.enabled {
+        if let Some(dir) = Path::new(&config.indexer.db_path).parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let store = IndexStore::open(&config.indexer.db_path).unwrap_or_else(|err| {
+            tracing::error!("Failed to open index database {}: {}", config.indexer.db_path, err);
+            process::exit(1);
+        });
+        indexer::spawn(RpcQuerier::new(client), store.clone(), &config.indexer);
+        state = state.with_index(store);
+    }
+
+    let app = router(state);
 
     let listener = tokio::net::TcpListener::bind(format!("{}:{}", config.bind_ip, config.port)).await.unwrap();
-    println!("Server listening {}:{}", config.bind_ip, config.port);
+    tracing::info!("Server listening {}:{}", config.bind_ip, config.port);
+
+    // Stop accepting connections on SIGTERM/SIGINT and let in-flight requests drain
+    let shutdown = Arc::new(Notify::new());
+    let server = axum::serve(listener, app).with_graceful_shutdown({
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown_signal().await;
+            let _ = stop_streams.send(true);
+            shutdown.notify_one();
+        }
+    });
 
-    axum::serve(listener, app).await.unwrap();
+    let drain_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    tokio::select! {
+        result = server.into_future() => {
+            if let Err(err) = result {
+                tracing::error!("Server error: {}", err);
+                process::exit(1);
+            }
+            tracing::info!("Server stopped, all in-flight requests drained");
+        }
+        _ = async {
+            shutdown.notified().await;
+            tokio::time::sleep(drain_timeout).await;
+        } => {
+            tracing::warn!("Drain deadline of {}s elapsed, forcing shutdown", config.shutdown_timeout_secs);
+            process::exit(1);
+        }
+    }
 }
 
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received, draining in-flight requests");
+}
+
+fn init_tracing(config: &Settings) {
+    let filter = EnvFilter::try_new(&config.log_level).unwrap_or_else(|err| {
+        eprintln!("Invalid log_level {:?}: {}, falling back to \"info\"", config.log_level, err);
+        EnvFilter::new("info")
+    });
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match config.log_format.as_str() {
+        "json" => subscriber.json().with_current_span(true).init(),
+        _ => subscriber.pretty().init(),
+    }
+}
 
 fn read_settings_from_file<P: AsRef<Path>>(path: P) -> Result<Settings, Box<dyn std::error::Error>> {
     let settings_str = fs::read_to_string(path)?;
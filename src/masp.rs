@@ -0,0 +1,93 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use futures::future;
+use namada_sdk::parameters::storage::get_epochs_per_year_key;
+use namada_sdk::token::storage_key::{masp_last_inflation_key, masp_last_locked_amount_key};
+use namada_sdk::types::address::{Address, InternalAddress};
+use namada_sdk::types::token;
+use serde_json::{json, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{ErrorResponse, MaspConversionsResponse, MaspRewardsResponse};
+use crate::query::{
+    balance_of, conversions_to_json, current_epoch, dec_to_f64, query_as, query_storage, ratio, to_f64, AtEpoch,
+    MyErrorWrapper, RPCRequestType, RPCResult,
+};
+use crate::ServerState;
+
+#[utoipa::path(
+    get,
+    path = "/masp/conversions",
+    params(("epoch" = Option<u64>, Query, description = "Epoch to list conversions for, defaults to the current epoch")),
+    responses(
+        (status = 200, description = "The MASP conversions of an epoch: what each shielded asset converts into, rewards included.", body = MaspConversionsResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_masp_conversions<C: RpcClient>(State(state): State<ServerState<C>>,
+                                                Query(at): Query<AtEpoch>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (epoch, conversions) = futures::try_join!(
+        at.resolve(client),
+        query_as(client, RPCRequestType::QueryConversions, |result| match result {
+            RPCResult::Conversions(conversions) => Some(conversions),
+            _ => None,
+        }),
+    )?;
+
+    Ok(Json(json!({
+        "epoch": epoch,
+        "data": conversions_to_json(&conversions, Some(epoch)),
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/masp/rewards",
+    responses(
+        (status = 200, description = "Effective MASP reward rate and locked amount per reward token, next to the controller parameters.", body = MaspRewardsResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_masp_rewards<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (epoch, epochs_per_year, tokens) = futures::try_join!(
+        current_epoch(client),
+        query_storage::<_, u64>(client, get_epochs_per_year_key()),
+        query_as(client, RPCRequestType::QueryMaspReward, |result| match result {
+            RPCResult::MapsReward(rewards) => Some(rewards),
+            _ => None,
+        }),
+    )?;
+    let epochs_per_year = epochs_per_year.unwrap_or_default();
+
+    let data = future::try_join_all(tokens.into_iter().map(|reward| async move {
+        let (locked, last_locked, last_inflation) = futures::try_join!(
+            balance_of(client, &reward.address, Address::Internal(InternalAddress::Masp)),
+            query_storage::<_, token::Amount>(client, masp_last_locked_amount_key(&reward.address)),
+            query_storage::<_, token::Amount>(client, masp_last_inflation_key(&reward.address)),
+        )?;
+        let (last_locked, last_inflation) = (last_locked.unwrap_or_default(), last_inflation.unwrap_or_default());
+        // Rewards minted last epoch over what was shielded when they were computed
+        let epoch_rate = ratio(to_f64(last_inflation), to_f64(last_locked));
+
+        Ok::<_, MyErrorWrapper>(json!({
+            "name": reward.name,
+            "address": reward.address,
+            "locked_amount": locked,
+            "last_locked_amount": last_locked,
+            "last_inflation": last_inflation,
+            "epoch_reward_rate": epoch_rate,
+            "annual_reward_rate": epoch_rate * epochs_per_year as f64,
+            "max_reward_rate": dec_to_f64(reward.max_reward_rate),
+            "locked_amount_target": reward.locked_amount_target.to_string(),
+        }))
+    }))
+    .await?;
+
+    Ok(Json(json!({
+        "epoch": epoch,
+        "epochs_per_year": epochs_per_year,
+        "data": data,
+    })))
+}
@@ -0,0 +1,538 @@
+// The handlers build their bodies with `json!`, so these envelopes are never constructed and only
+// describe those shapes in the OpenAPI document. Keep them in sync with `get_rpc_data`.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use serde::Serialize;
+use utoipa::openapi::{ObjectBuilder, OneOfBuilder, RefOr, Schema, SchemaType};
+use utoipa::{OpenApi, ToSchema};
+
+use crate::account;
+use crate::batch::{self, BatchRequest};
+use crate::eth_bridge;
+use crate::fee::{self, EstimateFeeRequest};
+use crate::ibc;
+use crate::indexer;
+use crate::masp;
+use crate::parameters;
+use crate::shielded::{self, ShieldedBalanceRequest};
+use crate::sse;
+use crate::staking;
+use crate::supply;
+use crate::validator;
+use crate::query::{
+    self, CommissionPairWrapper, EventSerializable, GovernanceParametersWrapper, MaspTokenRewardDataWrapper,
+    SerializableLastBlock, SerializableOwnedPosParams, SerializablePosParams, VoteWrapper,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    error: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct EpochResponse {
+    epoch: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct EpochAtHeightResponse {
+    /// The epoch, or the string "None" when the height is not known yet
+    #[schema(schema_with = epoch_or_none)]
+    epoch: serde_json::Value,
+}
+
+fn epoch_or_none() -> RefOr<Schema> {
+    RefOr::T(Schema::OneOf(
+        OneOfBuilder::new()
+            .item(ObjectBuilder::new().schema_type(SchemaType::Integer).minimum(Some(0.0)))
+            .item(ObjectBuilder::new().schema_type(SchemaType::String).enum_values(Some(["None"])))
+            .build(),
+    ))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProposalResultResponse {
+    result: String,
+    total_voting_power: String,
+    total_yay_power: String,
+    total_nay_power: String,
+    total_abstain_power: String,
+    threshold: String,
+    thresh_frac: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProposalVotesResponse {
+    data: Vec<VoteWrapper>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BalanceResponse {
+    balance: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ValidatorStateResponse {
+    /// One of Consensus, BelowCapacity, BelowThreshold, Inactive or Jailed
+    state: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DelegatorDelegationResponse {
+    /// Validator addresses
+    data: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DelegatorDelegationAtResponse {
+    /// Bonded amount keyed by validator address
+    data: HashMap<String, String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MetaDataResponse {
+    #[schema(value_type = Option<Object>)]
+    metadata: Option<serde_json::Value>,
+    commission: Option<CommissionPairWrapper>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GovernanceParametersResponse {
+    data: GovernanceParametersWrapper,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PosParametersResponse {
+    data: SerializablePosParams,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BoolResponse {
+    data: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ConsensusKeyResponse {
+    data: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TxEventResponse {
+    data: EventSerializable,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AddressResponse {
+    address: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LatestBlockResponse {
+    data: SerializableLastBlock,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MaspRewardResponse {
+    data: Vec<MaspTokenRewardDataWrapper>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TotalResponse {
+    total: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AccountResponse {
+    address: String,
+    /// implicit, established or internal
+    kind: String,
+    epoch: u64,
+    /// Balance keyed by token address: the native token and the MASP reward tokens
+    balances: HashMap<String, String>,
+    /// `{"index", "public_key"}` pairs, null when the account has no keys on chain
+    #[schema(value_type = Option<Vec<Object>>)]
+    public_keys: Option<Vec<serde_json::Value>>,
+    threshold: Option<u8>,
+    /// `validator`, `delegator` and `steward` flags
+    #[schema(value_type = Object)]
+    roles: serde_json::Value,
+    /// Bonded by this address across all validators, net of slashes
+    total_bonded: String,
+    /// `{"validator", "amount", "withdrawable_epoch"}` for unbonds not yet withdrawable
+    #[schema(value_type = Vec<Object>)]
+    pending_unbonds: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AccountKeysResponse {
+    address: String,
+    kind: String,
+    /// Signatures needed to authorize a tx, null when the account has no keys on chain
+    threshold: Option<u8>,
+    /// `{"index", "public_key"}` pairs in signing index order
+    #[schema(value_type = Vec<Object>)]
+    public_keys: Vec<serde_json::Value>,
+    /// Hash of the account's VP code; null for implicit and internal addresses, which have no VP of their own
+    vp_code_hash: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ValidatorResponse {
+    address: String,
+    /// Current epoch, which the profile fields describe
+    epoch: u64,
+    state: Option<String>,
+    stake: String,
+    commission: Option<CommissionPairWrapper>,
+    #[schema(value_type = Option<Object>)]
+    metadata: Option<serde_json::Value>,
+    consensus_key: Option<String>,
+    /// `{"epoch", "state", "stake", "commission"}` per epoch of the requested range
+    #[schema(value_type = Vec<Object>)]
+    series: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ValidatorDelegatorsResponse {
+    address: String,
+    epoch: u64,
+    /// Delegators with a non-zero bond at `epoch`, across all pages
+    total: usize,
+    /// `{"delegator", "amount", "self_bond"}`, largest amount first
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+    limit: u64,
+    offset: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ValidatorLivenessResponse {
+    address: String,
+    state: Option<String>,
+    /// Votes missed within the last `liveness_window_check` blocks
+    missed_votes: u64,
+    liveness_window_check: u64,
+    liveness_threshold: String,
+    /// Missed votes in one window at which the validator is jailed
+    max_missed_votes: u64,
+    /// Votes the validator can still miss without reaching `max_missed_votes`
+    remaining_missed_votes: u64,
+    formula: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ValidatorCommissionResponse {
+    address: String,
+    /// Current epoch; later epochs in the series are already scheduled but not yet in effect
+    epoch: u64,
+    pipeline_len: u64,
+    /// `{"epoch", "pending", "commission"}` per epoch of the range
+    #[schema(value_type = Vec<Object>)]
+    series: Vec<serde_json::Value>,
+    /// `{"epoch", "from", "to", "pending"}` for every epoch whose rate differs from the one before
+    #[schema(value_type = Vec<Object>)]
+    changes: Vec<serde_json::Value>,
+    /// Whether a scheduled change raises the rate above the current one
+    upcoming_increase: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct StakingAprResponse {
+    /// Current yearly reward rate for stake, before commission
+    apr: f64,
+    /// Upper bound allowed by `max_inflation_rate`
+    max_apr: f64,
+    /// epoch, epochs_per_year, last_inflation, total_supply, total_staked, staked_ratio,
+    /// max_inflation_rate and target_staked_ratio
+    #[schema(value_type = Object)]
+    inputs: serde_json::Value,
+    formula: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ValidatorAprResponse {
+    address: String,
+    commission_rate: f64,
+    stake: String,
+    /// `gross_apr` net of the validator's commission
+    apr: f64,
+    gross_apr: f64,
+    #[schema(value_type = Object)]
+    inputs: serde_json::Value,
+    formula: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SupplyResponse {
+    token: String,
+    epoch: u64,
+    /// Everything ever minted, less what was burned
+    total_supply: String,
+    /// Balance of the PoS account: bonded and unbonding tokens
+    locked_in_pos: String,
+    /// Balance of the shielded pool
+    locked_in_masp: String,
+    pgf_treasury: String,
+    /// Minted last epoch as PoS rewards (native token only, null otherwise) and MASP rewards
+    #[schema(value_type = Object)]
+    last_inflation: serde_json::Value,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MaspConversionsResponse {
+    epoch: u64,
+    /// `{"asset_type", "token", "denom", "epoch", "conversion"}`, where `conversion` maps asset
+    /// types to signed amounts
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MaspRewardsResponse {
+    epoch: u64,
+    epochs_per_year: u64,
+    /// Per reward token: name, address, locked_amount, last_locked_amount, last_inflation,
+    /// epoch_reward_rate (last_inflation / last_locked_amount), annual_reward_rate,
+    /// max_reward_rate and locked_amount_target
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ShieldedBalanceResponse {
+    /// Epoch the balances were converted to
+    epoch: u64,
+    /// `{"token", "amount"}` per token the viewing key holds
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IbcChannelsResponse {
+    /// `{"port_id", "channel_id", "state", "ordering", "counterparty_port_id",
+    /// "counterparty_channel_id", "connection_hops", "version"}`
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IbcDenomResponse {
+    /// `{"trace", "path", "base_denom", "ibc_denom", "token"}`, null when no received token has this hash
+    #[schema(value_type = Option<Object>)]
+    data: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IbcBalancesResponse {
+    owner: String,
+    /// The denom fields of `/ibc/denom` plus `amount`
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BridgePoolResponse {
+    /// Pending transfers as the node serializes them: `{"transfer": {...}, "gas_fee": {...}}`
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BridgeContractsResponse {
+    bridge: String,
+    native_erc20: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BridgeWhitelistResponse {
+    /// `{"erc20", "whitelisted", "supply", "cap"}` per ERC20 token
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ParametersResponse {
+    /// `{"min_num_of_blocks", "min_duration_secs"}`; an epoch ends once both have passed
+    #[schema(value_type = Option<Object>)]
+    epoch_duration: Option<serde_json::Value>,
+    max_expected_time_per_block_secs: Option<u64>,
+    epochs_per_year: Option<u64>,
+    max_block_gas: Option<u64>,
+    max_tx_bytes: Option<u32>,
+    max_proposal_bytes: Option<u64>,
+    /// Tokens accepted for gas fees
+    fee_tokens: Vec<String>,
+    /// Minimum gas price per whole gas unit keyed by fee token, as a raw integer amount of the token
+    min_gas_prices: HashMap<String, String>,
+    implicit_vp_code_hash: Option<String>,
+    /// Allowed tx code hashes; empty allows any
+    tx_whitelist: Vec<String>,
+    /// Allowed VP code hashes; empty allows any
+    vp_whitelist: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct EstimateFeeResponse {
+    kind: Option<String>,
+    /// `table` for a kind's gas limit, `dry_run` when the tx was run through the node
+    source: String,
+    gas: u64,
+    /// Fee keyed by fee token: gas times the token's minimum gas price, as a raw integer amount
+    fees: HashMap<String, String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IndexStatusResponse {
+    last_indexed_height: Option<u64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IndexedTransfersResponse {
+    /// `{"tx_hash", "height", "source", "target", "token", "amount", "status"}`, newest first
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+    limit: u64,
+    offset: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IndexedVotesResponse {
+    /// `{"tx_hash", "height", "proposal_id", "voter", "vote", "status"}`, newest first
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+    limit: u64,
+    offset: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AccountTxsResponse {
+    /// `{"hash", "height", "kind", "status", "code", "applied_height", "roles"}`, one per tx, newest first
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+    limit: u64,
+    offset: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BatchResponse {
+    /// One `{"ok": ...}` or `{"error": ...}` object per query, in request order
+    #[schema(value_type = Vec<Object>)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Namada REST API",
+        description = "REST access to a Namada node's RPC queries. The GraphQL endpoint (`POST /graphql`, \
+            with a GraphiQL page on `GET /graphql`) and the `/ws` WebSocket subscriptions are not described here."
+    ),
+    paths(
+        query::get_epoch,
+        query::get_epoch_at_height,
+        query::get_proposals,
+        query::get_proposal_votes,
+        query::get_balance,
+        query::get_validator_state,
+        query::get_delegators_delegation,
+        query::get_delegators_delegation_at,
+        query::get_meta_data,
+        query::get_governance_parameters,
+        query::get_pos_parameters,
+        query::check_steward,
+        query::get_validator_consensus_keys,
+        query::get_tx_events,
+        sse::stream_tx_event,
+        query::get_native_token,
+        query::get_latest_block,
+        query::check_is_validator,
+        query::check_is_delegator,
+        query::get_masp_reward,
+        query::get_total_staked_tokens,
+        query::get_validator_stake,
+        account::get_account,
+        account::get_account_keys,
+        validator::get_validator,
+        validator::get_validator_delegators,
+        validator::get_validator_liveness,
+        validator::get_validator_commission,
+        staking::get_staking_apr,
+        staking::get_validator_apr,
+        supply::get_supply,
+        masp::get_masp_conversions,
+        masp::get_masp_rewards,
+        shielded::post_shielded_balance,
+        ibc::get_ibc_channels,
+        ibc::get_ibc_denom,
+        ibc::get_ibc_balances,
+        eth_bridge::get_bridge_pool,
+        eth_bridge::get_bridge_contracts,
+        eth_bridge::get_bridge_whitelist,
+        parameters::get_parameters,
+        fee::post_estimate_fee,
+        indexer::get_index_status,
+        indexer::get_transfers,
+        indexer::get_votes,
+        indexer::get_account_txs,
+        batch::post_batch,
+    ),
+    components(schemas(
+        CommissionPairWrapper,
+        GovernanceParametersWrapper,
+        SerializableOwnedPosParams,
+        SerializablePosParams,
+        VoteWrapper,
+        EventSerializable,
+        SerializableLastBlock,
+        MaspTokenRewardDataWrapper,
+        ErrorResponse,
+        EpochResponse,
+        EpochAtHeightResponse,
+        ProposalResultResponse,
+        ProposalVotesResponse,
+        BalanceResponse,
+        ValidatorStateResponse,
+        DelegatorDelegationResponse,
+        DelegatorDelegationAtResponse,
+        MetaDataResponse,
+        GovernanceParametersResponse,
+        PosParametersResponse,
+        BoolResponse,
+        ConsensusKeyResponse,
+        TxEventResponse,
+        AddressResponse,
+        LatestBlockResponse,
+        MaspRewardResponse,
+        TotalResponse,
+        AccountResponse,
+        AccountKeysResponse,
+        ValidatorResponse,
+        ValidatorDelegatorsResponse,
+        ValidatorLivenessResponse,
+        ValidatorCommissionResponse,
+        StakingAprResponse,
+        ValidatorAprResponse,
+        SupplyResponse,
+        MaspConversionsResponse,
+        MaspRewardsResponse,
+        ShieldedBalanceRequest,
+        ShieldedBalanceResponse,
+        IbcChannelsResponse,
+        IbcDenomResponse,
+        IbcBalancesResponse,
+        BridgePoolResponse,
+        BridgeContractsResponse,
+        BridgeWhitelistResponse,
+        ParametersResponse,
+        EstimateFeeRequest,
+        EstimateFeeResponse,
+        IndexStatusResponse,
+        IndexedTransfersResponse,
+        IndexedVotesResponse,
+        AccountTxsResponse,
+        BatchRequest,
+        BatchResponse,
+    ))
+)]
+pub struct ApiDoc;
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use axum::extract::State;
+use axum::Json;
+use namada_sdk::parameters::storage as params_storage;
+use namada_sdk::parameters::EpochDuration;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::chain::ProposalBytes;
+use namada_sdk::types::hash::Hash;
+use namada_sdk::types::time::DurationSecs;
+use namada_sdk::types::token;
+use serde_json::{json, Map, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{ErrorResponse, ParametersResponse};
+use crate::query::{gas_prices, query_storage, MyErrorWrapper};
+use crate::ServerState;
+
+pub(crate) fn gas_prices_json(gas_prices: &BTreeMap<Address, token::Amount>) -> Value {
+    Value::Object(
+        gas_prices
+            .iter()
+            .map(|(token, price)| (token.to_string(), json!(price)))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/parameters",
+    responses(
+        (status = 200, description = "Protocol parameters: epoch duration, block and tx limits, gas prices per fee token, implicit VP and the tx/VP whitelists.", body = ParametersResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_parameters<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (
+        epoch_duration,
+        max_expected_time_per_block,
+        epochs_per_year,
+        max_block_gas,
+        max_tx_bytes,
+        max_proposal_bytes,
+        gas_prices,
+        implicit_vp_code_hash,
+        tx_whitelist,
+        vp_whitelist,
+    ) = futures::try_join!(
+        query_storage::<_, EpochDuration>(client, params_storage::get_epoch_duration_storage_key()),
+        query_storage::<_, DurationSecs>(client, params_storage::get_max_expected_time_per_block_key()),
+        query_storage::<_, u64>(client, params_storage::get_epochs_per_year_key()),
+        query_storage::<_, u64>(client, params_storage::get_max_block_gas_key()),
+        query_storage::<_, u32>(client, params_storage::get_max_tx_bytes_key()),
+        query_storage::<_, ProposalBytes>(client, params_storage::get_max_proposal_bytes_key()),
+        gas_prices(client),
+        query_storage::<_, Hash>(client, params_storage::get_implicit_vp_key()),
+        query_storage::<_, Vec<String>>(client, params_storage::get_tx_whitelist_storage_key()),
+        query_storage::<_, Vec<String>>(client, params_storage::get_vp_whitelist_storage_key()),
+    )?;
+
+    Ok(Json(json!({
+        "epoch_duration": epoch_duration.map(|duration| json!({
+            "min_num_of_blocks": duration.min_num_of_blocks,
+            "min_duration_secs": duration.min_duration.0,
+        })),
+        "max_expected_time_per_block_secs": max_expected_time_per_block.map(|duration| duration.0),
+        "epochs_per_year": epochs_per_year,
+        "max_block_gas": max_block_gas,
+        "max_tx_bytes": max_tx_bytes,
+        "max_proposal_bytes": max_proposal_bytes.map(|bytes| bytes.get()),
+        "fee_tokens": gas_prices.keys().collect::<Vec<_>>(),
+        "min_gas_prices": gas_prices_json(&gas_prices),
+        "implicit_vp_code_hash": implicit_vp_code_hash.map(|hash| hash.to_string()),
+        // An empty whitelist allows any code
+        "tx_whitelist": tx_whitelist.unwrap_or_default(),
+        "vp_whitelist": vp_whitelist.unwrap_or_default(),
+    })))
+}
@@ -1,6 +1,8 @@
-mod constants;
+pub(crate) mod constants;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Instant;
+use borsh::BorshDeserialize;
 use axum::{
     extract::{Path, State},
     response::{IntoResponse, Response},
@@ -18,21 +20,40 @@ use namada_sdk::events::Event;
 use namada_sdk::governance::parameters::GovernanceParameters;
 use namada_sdk::governance::utils::Vote;
 use namada_sdk::masp::MaspTokenRewardData;
+use namada_sdk::parameters::storage as params_storage;
+use namada_sdk::masp_primitives::asset_type::AssetType;
+use namada_sdk::masp_primitives::transaction::components::I128Sum;
 use namada_sdk::proof_of_stake::{PosParams};
-use namada_sdk::proof_of_stake::types::{CommissionPair, ValidatorMetaData, ValidatorState};
+use namada_sdk::proof_of_stake::storage_key::liveness_sum_missed_votes_key;
+use namada_sdk::queries::{Client, RPC};
+use namada_sdk::proof_of_stake::types::{BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState};
 use namada_sdk::rpc::{TxEventQuery};
 use namada_sdk::state::{BlockHash, BlockHeight, LastBlock};
+use namada_sdk::types::account::Account;
 use namada_sdk::types::address::Address;
+use namada_sdk::types::eth_bridge_pool::PendingTransfer;
+use namada_sdk::types::ethereum_events::EthAddress;
+use namada_sdk::types::hash::Hash;
 use namada_sdk::types::key::common;
+use namada_sdk::types::storage::Key;
 use namada_sdk::types::time::DateTimeUtc;
 use namada_sdk::types::token;
+use namada_sdk::types::token::MaspDenom;
 use namada_sdk::types::uint::Uint;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::{json, Value};
-use tendermint_rpc::{self, HttpClient};
+use utoipa::ToSchema;
 
+use crate::openapi::{
+    AddressResponse, BalanceResponse, BoolResponse, ConsensusKeyResponse, DelegatorDelegationAtResponse,
+    DelegatorDelegationResponse, EpochAtHeightResponse, EpochResponse, ErrorResponse, GovernanceParametersResponse, LatestBlockResponse,
+    MaspRewardResponse, MetaDataResponse, PosParametersResponse, ProposalResultResponse, ProposalVotesResponse,
+    TotalResponse, TxEventResponse, ValidatorStateResponse,
+};
+use crate::client::RpcClient;
 use crate::ServerState;
 
+#[derive(Debug)]
 pub enum RPCRequestType {
     QueryEpoch,
     QueryEpochAtHeight(BlockHeight),
@@ -55,6 +76,21 @@ pub enum RPCRequestType {
     QueryMaspReward,
     QueryTotalStakedTokens(Epoch),
     QueryValidatorStaked(Epoch, Address),
+    QueryRewards(Option<Address>, Address),
+    QueryBlockResults(BlockHeight),
+    QueryTxEventStage(String),
+    QueryBlock(BlockHeight),
+    QueryAccountInfo(Address),
+    QueryBondsAndUnbonds(Option<Address>, Option<Address>),
+    QueryVpCodeHash(Address),
+    QueryMissedVotes(Address),
+    QueryStorageValue(Key),
+    QueryConversions,
+    QueryStoragePrefix(Key),
+    QueryBridgePool,
+    QueryBridgeContracts,
+    QueryErc20FlowControl(EthAddress),
+    QueryDryRun(Vec<u8>),
 }
 
 pub enum RPCResult {
@@ -79,16 +115,70 @@ pub enum RPCResult {
     MapsReward(Vec<MaspTokenRewardData>),
     TotalStakedTokens(token::Amount),
     ValidatorStake(token::Amount),
+    Rewards(token::Amount),
+    BlockResults(BlockHeight, Vec<EventSerializable>),
+    TxEventStage(Option<(TxEventStage, Event)>),
+    Block(RawBlock),
+    AccountInfo(Option<Account>),
+    BondsAndUnbonds(BondsAndUnbondsDetails),
+    VpCodeHash(Option<Hash>),
+    MissedVotes(Option<u64>),
+    StorageValue(Option<Vec<u8>>),
+    Conversions(Conversions),
+    StoragePrefix(Vec<(Key, Vec<u8>)>),
+    BridgePool(Vec<PendingTransfer>),
+    // Bridge contract and native ERC20 contract
+    BridgeContracts(EthAddress, EthAddress),
+    // Whitelisted, minted supply and cap
+    Erc20FlowControl(bool, token::Amount, token::Amount),
+    // Gas used by the dry-run tx, in whole gas units
+    DryRun(u64),
+}
+
+/// The MASP conversion table: for each asset type, its token, denomination, epoch and the
+/// amounts it converts into
+pub type Conversions = BTreeMap<AssetType, (Address, MaspDenom, Epoch, I128Sum)>;
+
+/// A committed block with its txs still in their wire encoding
+pub struct RawBlock {
+    pub height: BlockHeight,
+    pub hash: String,
+    pub time: String,
+    pub txs: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxEventStage {
+    Accepted,
+    Applied,
+}
+
+impl TxEventStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxEventStage::Accepted => "accepted",
+            TxEventStage::Applied => "applied",
+        }
+    }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct CommissionPairWrapper {
     commission_rate: String,
     max_commission_change_per_epoch: String,
 }
 
-#[derive(Serialize)]
-struct GovernanceParametersWrapper {
+impl From<CommissionPair> for CommissionPairWrapper {
+    fn from(commission: CommissionPair) -> Self {
+        CommissionPairWrapper {
+            commission_rate: format!("{}", commission.commission_rate),
+            max_commission_change_per_epoch: format!("{}", commission.max_commission_change_per_epoch),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GovernanceParametersWrapper {
     min_proposal_fund: String,
     max_proposal_code_size: String,
     min_proposal_voting_period: String,
@@ -97,7 +187,7 @@ struct GovernanceParametersWrapper {
     min_proposal_grace_epochs: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct SerializableOwnedPosParams {
     pub max_validator_slots: u64,
     pub pipeline_len: u64,
@@ -117,44 +207,58 @@ pub struct SerializableOwnedPosParams {
     pub rewards_gain_d: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct SerializablePosParams {
     pub owned: SerializableOwnedPosParams,
     pub max_proposal_period: u64,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct VoteWrapper {
     validator: String,
     delegator: String,
     data: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct EventSerializable {
-    event_type: String,
-    level: String,
-    attributes: HashMap<String, String>,
+    pub event_type: String,
+    pub level: String,
+    pub attributes: HashMap<String, String>,
 }
 
-#[derive(Serialize)]
-struct SerializableLastBlock {
+#[derive(Serialize, ToSchema)]
+pub struct SerializableLastBlock {
+    #[schema(value_type = u64)]
     height: BlockHeight,
+    #[schema(value_type = Vec<u8>)]
     hash: BlockHash,
+    #[schema(value_type = String)]
     time: DateTimeUtc,
 }
 
-#[derive(Serialize)]
-struct MaspTokenRewardDataWrapper {
+#[derive(Serialize, ToSchema)]
+pub struct MaspTokenRewardDataWrapper {
     pub name: String,
+    #[schema(value_type = String)]
     pub address: Address,
+    #[schema(value_type = String)]
     pub max_reward_rate: Dec,
+    #[schema(value_type = String)]
     pub kp_gain: Dec,
+    #[schema(value_type = String)]
     pub kd_gain: Dec,
+    #[schema(value_type = String)]
     pub locked_amount_target: Uint,
 }
 
-pub struct MyErrorWrapper(error::Error);
+pub struct MyErrorWrapper(pub error::Error);
+
+impl std::fmt::Display for MyErrorWrapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 // Implement `IntoResponse` for your new type
 impl IntoResponse for MyErrorWrapper {
@@ -170,16 +274,42 @@ impl IntoResponse for MyErrorWrapper {
     }
 }
 
-pub async fn get_epoch(State(state): State<ServerState>) -> Result<Json<Value>, MyErrorWrapper> {
+#[utoipa::path(
+    get,
+    path = "/epoch",
+    responses(
+        (status = 200, description = "Retrieves the current epoch.", body = EpochResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_epoch<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryEpoch).await
 }
 
-pub async fn get_epoch_at_height(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/epoch_at_height/{height}",
+    params(("height" = u64, Path, description = "Block height")),
+    responses(
+        (status = 200, description = "Query the epoch of the given block height, \"None\" when it is not known yet.", body = EpochAtHeightResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_epoch_at_height<C: RpcClient>(State(state): State<ServerState<C>>,
                                  Path(height): Path<BlockHeight>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryEpochAtHeight(height)).await
 }
 
-pub async fn get_balance(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/balance/{wallet}",
+    params(("wallet" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Query the native token balance of an owner.", body = BalanceResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_balance<C: RpcClient>(State(state): State<ServerState<C>>,
                          Path(owner): Path<Address>, ) -> Result<Json<Value>, MyErrorWrapper> {
     let decode = Address::decode(constants::NAAN_ADDRESS);
     match decode {
@@ -190,97 +320,289 @@ pub async fn get_balance(State(state): State<ServerState>,
     }
 }
 
-pub async fn get_validator_state(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/validator_state/{address}/{epoch}",
+    params(("address" = String, Path, description = "Validator address, e.g. tnam1q..."), ("epoch" = u64, Path, description = "Epoch")),
+    responses(
+        (status = 200, description = "Get the given validator's state at the given epoch.", body = ValidatorStateResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_state<C: RpcClient>(State(state): State<ServerState<C>>,
                                  Path((address, epoch)): Path<(Address, Epoch)>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryValidatorState(address, Some(epoch))).await
 }
 
-pub async fn get_delegators_delegation(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/delegator_delegation/{wallet}",
+    params(("wallet" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Get the validators a delegator has delegated to.", body = DelegatorDelegationResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_delegators_delegation<C: RpcClient>(State(state): State<ServerState<C>>,
                                        Path(delegator): Path<Address>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryDelegatorDelegation(delegator)).await
 }
 
-pub async fn get_delegators_delegation_at(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/delegator_delegation_at/{wallet}/{epoch}",
+    params(("wallet" = String, Path, description = "Account address, e.g. tnam1q..."), ("epoch" = u64, Path, description = "Epoch")),
+    responses(
+        (status = 200, description = "Get the delegator's delegations, including amounts, at the given epoch.", body = DelegatorDelegationAtResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_delegators_delegation_at<C: RpcClient>(State(state): State<ServerState<C>>,
                                           Path((address, epoch)): Path<(Address, Epoch)>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryDelegatorDelegationAt(address, epoch)).await
 }
 
-pub async fn get_meta_data(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/metadata/{address}/{epoch}",
+    params(("address" = String, Path, description = "Validator address, e.g. tnam1q..."), ("epoch" = u64, Path, description = "Epoch")),
+    responses(
+        (status = 200, description = "Query a validator's metadata, commission rate and max commission rate change.", body = MetaDataResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_meta_data<C: RpcClient>(State(state): State<ServerState<C>>,
                            Path((address, epoch)): Path<(Address, Epoch)>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryMetaData(address, Some(epoch))).await
 }
 
-pub async fn get_governance_parameters(State(state): State<ServerState>) -> Result<Json<Value>, MyErrorWrapper> {
+#[utoipa::path(
+    get,
+    path = "/governance",
+    responses(
+        (status = 200, description = "Get the governance parameters.", body = GovernanceParametersResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_governance_parameters<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryGovernanceParameters).await
 }
 
-pub async fn get_pos_parameters(State(state): State<ServerState>) -> Result<Json<Value>, MyErrorWrapper> {
+#[utoipa::path(
+    get,
+    path = "/pos_params",
+    responses(
+        (status = 200, description = "Get the PoS parameters.", body = PosParametersResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_pos_parameters<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryPosParameters).await
 }
 
-pub async fn check_steward(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/is_steward/{wallet}",
+    params(("wallet" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Check if the given address is a PGF steward.", body = BoolResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn check_steward<C: RpcClient>(State(state): State<ServerState<C>>,
                            Path(address): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryCheckIsSteward(address)).await
 }
 
-pub async fn get_proposals(
-    State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/proposal_result/{id}",
+    params(("id" = u32, Path, description = "Proposal ID")),
+    responses(
+        (status = 200, description = "Get the result of the proposal with the given ID.", body = ProposalResultResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_proposals<C: RpcClient>(
+    State(state): State<ServerState<C>>,
     Path(id): Path<u32>,
 ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryProposalResult(id as u64)).await
 }
 
-pub async fn get_proposal_votes(
-    State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/proposal_votes/{id}",
+    params(("id" = u32, Path, description = "Proposal ID")),
+    responses(
+        (status = 200, description = "Get all the votes of a proposal.", body = ProposalVotesResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_proposal_votes<C: RpcClient>(
+    State(state): State<ServerState<C>>,
     Path(id): Path<u32>,
 ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryProposalVotes(id as u64)).await
 }
 
-pub async fn get_validator_consensus_keys(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/validator_consensus_keys/{wallet}",
+    params(("wallet" = String, Path, description = "Validator address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Query the consensus key of a validator.", body = ConsensusKeyResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_consensus_keys<C: RpcClient>(State(state): State<ServerState<C>>,
                                           Path(address): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryValidatorConsensusKeys(address)).await
 }
 
-pub async fn get_tx_events(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/tx_event/{tx_hash}",
+    params(("tx_hash" = String, Path, description = "Transaction hash, hex encoded")),
+    responses(
+        (status = 200, description = "Fetch the Applied (or else Accepted) event of a transaction.", body = TxEventResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_tx_events<C: RpcClient>(State(state): State<ServerState<C>>,
                            Path(tx_hash): Path<String>) -> Result<Json<Value>, MyErrorWrapper> {
     {
         get_rpc_data(state.client, RPCRequestType::QueryTxEvents(tx_hash)).await
     }
 }
 
-pub async fn get_native_token(State(state): State<ServerState>) -> Result<Json<Value>, MyErrorWrapper> {
+#[utoipa::path(
+    get,
+    path = "/native_token",
+    responses(
+        (status = 200, description = "Query the address of the native token.", body = AddressResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_native_token<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryNativeToken).await
 }
 
-pub async fn get_latest_block(State(state): State<ServerState>) -> Result<Json<Value>, MyErrorWrapper> {
+#[utoipa::path(
+    get,
+    path = "/query_block",
+    responses(
+        (status = 200, description = "Query the last committed block.", body = LatestBlockResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_latest_block<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryLatestBlock).await
 }
 
-pub async fn check_is_validator(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/is_validator/{wallet}",
+    params(("wallet" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Check if the given address is a known validator.", body = BoolResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn check_is_validator<C: RpcClient>(State(state): State<ServerState<C>>,
                                 Path(address): Path<Address>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryCheckIsValidator(address)).await
 }
 
-pub async fn check_is_delegator(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/is_delegator/{wallet}",
+    params(("wallet" = String, Path, description = "Account address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Check if the given address is a known delegator.", body = BoolResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn check_is_delegator<C: RpcClient>(State(state): State<ServerState<C>>,
                                 Path(address): Path<Address>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryCheckIsDelegator(address)).await
 }
 
-pub async fn get_masp_reward(State(state): State<ServerState>) -> Result<Json<Value>, MyErrorWrapper> {
+#[utoipa::path(
+    get,
+    path = "/masp_reward",
+    responses(
+        (status = 200, description = "Query the tokens that earn MASP rewards.", body = MaspRewardResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_masp_reward<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryMaspReward).await
 }
 
-pub async fn get_total_staked_tokens(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/total_staked/{epoch}",
+    params(("epoch" = u64, Path, description = "Epoch")),
+    responses(
+        (status = 200, description = "Get the total staked tokens at the given epoch.", body = TotalResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_total_staked_tokens<C: RpcClient>(State(state): State<ServerState<C>>,
                                      Path(epoch): Path<Epoch>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryTotalStakedTokens(epoch)).await
 }
 
-pub async fn get_validator_stake(State(state): State<ServerState>,
+#[utoipa::path(
+    get,
+    path = "/validator_stake/{address}/{epoch}",
+    params(("address" = String, Path, description = "Validator address, e.g. tnam1q..."), ("epoch" = u64, Path, description = "Epoch")),
+    responses(
+        (status = 200, description = "Get the given validator's stake at the given epoch.", body = TotalResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_stake<C: RpcClient>(State(state): State<ServerState<C>>,
                                  Path((address, epoch)): Path<(Address, Epoch)>, ) -> Result<Json<Value>, MyErrorWrapper> {
     get_rpc_data(state.client, RPCRequestType::QueryValidatorStaked(epoch, address)).await
 }
 
+const DEFAULT_PAGE_SIZE: u64 = 50;
+const MAX_PAGE_SIZE: u64 = 500;
+
+/// `?limit=&offset=` paging for list routes
+#[derive(Deserialize)]
+pub struct Page {
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl Page {
+    pub(crate) fn limit(&self) -> u64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset.unwrap_or(0)
+    }
+}
+
+/// `?epoch=`, the current epoch when omitted
+#[derive(Deserialize)]
+pub struct AtEpoch {
+    epoch: Option<u64>,
+}
+
+impl AtEpoch {
+    pub(crate) async fn resolve<C: RpcClient>(&self, client: &C) -> Result<Epoch, MyErrorWrapper> {
+        match self.epoch {
+            Some(epoch) => Ok(Epoch(epoch)),
+            None => current_epoch(client).await,
+        }
+    }
+}
+
 pub fn serialize<S>(public_key: &Option<common::PublicKey>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -292,7 +614,44 @@ pub fn serialize<S>(public_key: &Option<common::PublicKey>, serializer: S) -> Re
     }
 }
 
-fn to_serializable(event: Event) -> EventSerializable {
+// Key index (as used in signatures) to public key, in index order
+pub(crate) fn public_keys_by_index(account: &Account) -> Vec<Value> {
+    let mut keys = account.public_keys_map.idx_to_pk.iter().collect::<Vec<_>>();
+    keys.sort_by_key(|(idx, _)| **idx);
+    keys.into_iter()
+        .map(|(idx, public_key)| json!({ "index": idx, "public_key": public_key.to_string() }))
+        .collect()
+}
+
+// Conversions keyed by asset type, optionally only those for one epoch
+pub(crate) fn conversions_to_json(conversions: &Conversions, epoch: Option<Epoch>) -> Vec<Value> {
+    conversions
+        .iter()
+        .filter(|(_, (_, _, asset_epoch, _))| epoch.map_or(true, |epoch| *asset_epoch == epoch))
+        .map(|(asset_type, (token, denom, asset_epoch, conversion))| json!({
+            "asset_type": asset_type.to_string(),
+            "token": token,
+            "denom": *denom as u8,
+            "epoch": asset_epoch,
+            "conversion": conversion
+                .components()
+                .map(|(asset_type, amount)| (asset_type.to_string(), json!(amount.to_string())))
+                .collect::<serde_json::Map<_, _>>(),
+        }))
+        .collect()
+}
+
+pub(crate) fn validator_state_name(state: &ValidatorState) -> &'static str {
+    match state {
+        ValidatorState::Consensus => "Consensus",
+        ValidatorState::BelowCapacity => "BelowCapacity",
+        ValidatorState::BelowThreshold => "BelowThreshold",
+        ValidatorState::Inactive => "Inactive",
+        ValidatorState::Jailed => "Jailed",
+    }
+}
+
+pub(crate) fn to_serializable(event: Event) -> EventSerializable {
     EventSerializable {
         event_type: format!("{:?}", event.event_type),
         level: format!("{:?}", event.level),
@@ -335,12 +694,164 @@ fn convert_to_serializable_pos(pos_params: PosParams) -> SerializablePosParams {
     }
 }
 
-// We need to do all this mess only because rpc::query_something is !Send which is a requirment for axum
-pub async fn get_rpc_data(
-    client: HttpClient,
+/// Run a query and unwrap the one result variant `extract` accepts, for handlers that combine
+/// several queries into one response
+pub(crate) async fn query_as<C: RpcClient, T>(
+    client: &C,
+    req_type: RPCRequestType,
+    extract: impl FnOnce(RPCResult) -> Option<T>,
+) -> Result<T, MyErrorWrapper> {
+    extract(query_rpc(client.clone(), req_type).await?)
+        .ok_or_else(|| MyErrorWrapper(error::Error::Other("Unexpected RPC result.".to_string())))
+}
+
+/// Read and borsh-decode a raw storage value, for state the SDK has no query helper for
+pub(crate) async fn query_storage<C: RpcClient, T: BorshDeserialize>(
+    client: &C,
+    key: Key,
+) -> Result<Option<T>, MyErrorWrapper> {
+    let bytes = query_as(client, RPCRequestType::QueryStorageValue(key.clone()), |result| match result {
+        RPCResult::StorageValue(bytes) => Some(bytes),
+        _ => None,
+    })
+    .await?;
+    bytes
+        .map(|bytes| T::try_from_slice(&bytes))
+        .transpose()
+        .map_err(|err| MyErrorWrapper(error::Error::Other(format!("Invalid value under {}: {}", key, err))))
+}
+
+pub(crate) async fn native_token<C: RpcClient>(client: &C) -> Result<Address, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryNativeToken, |result| match result {
+        RPCResult::NativeToken(token) => Some(token),
+        _ => None,
+    })
+    .await
+}
+
+pub(crate) async fn current_epoch<C: RpcClient>(client: &C) -> Result<Epoch, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryEpoch, |result| match result {
+        RPCResult::Epoch(epoch) => Some(epoch),
+        _ => None,
+    })
+    .await
+}
+
+pub(crate) async fn validator_stake<C: RpcClient>(client: &C, address: &Address, epoch: Epoch) -> Result<token::Amount, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryValidatorStaked(epoch, address.clone()), |result| match result {
+        RPCResult::ValidatorStake(amount) | RPCResult::TotalStakedTokens(amount) => Some(amount),
+        _ => None,
+    })
+    .await
+}
+
+pub(crate) async fn validator_metadata<C: RpcClient>(
+    client: &C,
+    address: &Address,
+    epoch: Epoch,
+) -> Result<(Option<ValidatorMetaData>, Option<CommissionPair>), MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryMetaData(address.clone(), Some(epoch)), |result| match result {
+        RPCResult::MetaData(metadata) => Some(metadata),
+        _ => None,
+    })
+    .await
+}
+
+pub(crate) async fn pos_params<C: RpcClient>(client: &C) -> Result<PosParams, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryPosParameters, |result| match result {
+        RPCResult::PosParameters(params) => Some(params),
+        _ => None,
+    })
+    .await
+}
+
+pub(crate) async fn balance_of<C: RpcClient>(client: &C, token: &Address, owner: Address) -> Result<token::Amount, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryBalance(token.clone(), owner), |result| match result {
+        RPCResult::BalanceResult(amount) => Some(amount),
+        _ => None,
+    })
+    .await
+}
+
+/// Minimum gas price per fee token. Only tokens listed here can pay for gas.
+pub(crate) async fn gas_prices<C: RpcClient>(client: &C) -> Result<BTreeMap<Address, token::Amount>, MyErrorWrapper> {
+    Ok(query_storage(client, params_storage::get_gas_cost_key()).await?.unwrap_or_default())
+}
+
+// Rates and APRs are estimates, so plain floats are precise enough and easier for clients to use
+// Goes through the decimal string, as amounts can exceed u128
+pub(crate) fn to_f64(amount: token::Amount) -> f64 {
+    amount.raw_amount().to_string().parse().unwrap_or_default()
+}
+
+pub(crate) fn dec_to_f64(dec: Dec) -> f64 {
+    dec.to_string().parse().unwrap_or_default()
+}
+
+pub(crate) fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 { numerator / denominator } else { 0.0 }
+}
+
+pub async fn get_rpc_data<C: RpcClient>(
+    client: C,
     req_type: RPCRequestType,
 ) -> Result<Json<Value>, MyErrorWrapper> {
+    query_rpc(client, req_type).await.map(render_rpc_result)
+}
+
+// Look for the Applied event first and fall back to the Accepted one, which is all a tx has
+// until its block is committed
+async fn find_tx_event<C: Client + Sync>(
+    client: &C,
+    tx_hash: &str,
+) -> Result<Option<(TxEventStage, Event)>, error::Error> {
+    let to_error = |err: C::Error| error::Error::Other(format!("Error to find tx events for your transaction: {}", err));
+    if let Some(event) = rpc::query_tx_events(client, TxEventQuery::Applied(tx_hash)).await.map_err(to_error)? {
+        return Ok(Some((TxEventStage::Applied, event)));
+    }
+    Ok(rpc::query_tx_events(client, TxEventQuery::Accepted(tx_hash))
+        .await
+        .map_err(to_error)?
+        .map(|event| (TxEventStage::Accepted, event)))
+}
+
+// The PoS liveness record is a lazy map from validator to missed votes in the current window,
+// so each entry lives under `<map key>/data/<validator>`. Validators without an entry have none.
+async fn query_missed_votes<C: Client + Sync>(client: &C, validator: &Address) -> Result<Option<u64>, error::Error> {
+    let key = liveness_sum_missed_votes_key()
+        .push(&"data".to_owned())
+        .and_then(|key| key.push(validator))
+        .map_err(|err| error::Error::Other(err.to_string()))?;
+    let (bytes, _) = rpc::query_storage_value_bytes(client, &key, None, false).await?;
+    bytes
+        .map(|bytes| u64::try_from_slice(&bytes))
+        .transpose()
+        .map_err(|err| error::Error::Other(format!("Invalid missed votes value: {}", err)))
+}
+
+// The gas meter counts sub-units; `gas_used` in tx events, gas limits and fees are in whole
+// units, rounded up
+pub(crate) const GAS_SCALE: u64 = 10_000;
+
+pub(crate) fn whole_gas_units(sub_units: u64) -> u64 {
+    sub_units.div_ceil(GAS_SCALE)
+}
+
+// We need to do all this mess only because rpc::query_something is !Send which is a requirment for axum
+pub async fn query_rpc<C: RpcClient>(
+    client: C,
+    req_type: RPCRequestType,
+) -> Result<RPCResult, MyErrorWrapper> {
+    // The blocking thread doesn't inherit the request span, so carry it over explicitly
+    let span = tracing::Span::current();
+    let request = match &req_type {
+        // A serialized tx can carry whole wasm blobs, so only log its size
+        RPCRequestType::QueryDryRun(tx_bytes) => format!("QueryDryRun({} bytes)", tx_bytes.len()),
+        req_type => format!("{:?}", req_type),
+    };
+    let started = Instant::now();
     let result = tokio::task::spawn_blocking(move || {
+        let _entered = span.enter();
         // Execute the blocking operation
         tokio::runtime::Handle::current().block_on(async {
             match req_type {
@@ -383,21 +894,14 @@ pub async fn get_rpc_data(
                 RPCRequestType::QueryValidatorConsensusKeys(address) => rpc::query_validator_consensus_keys(&client, &address)
                     .await
                     .map(RPCResult::ValidatorConsensusKeys),
-                RPCRequestType::QueryTxEvents(tx_hash) => {
-
-                    // In case search event_type Applied return None then we will search with Accepted
-                    match rpc::query_tx_events(&client, TxEventQuery::Applied(&tx_hash)).await {
-                        Ok(Some(event)) => Ok(RPCResult::TxEvents(event)),
-                        Ok(None) => {
-                            match rpc::query_tx_events(&client, TxEventQuery::Accepted(&tx_hash)).await {
-                                Ok(Some(event)) => Ok(RPCResult::TxEvents(event)),
-                                Ok(None) => Err(error::Error::Other("Unable to find tx events for your transaction.".to_string())),
-                                Err(err) => Err(error::Error::Other("Error to find tx events for your transaction.".to_string())),
-                            }
-                        }
-                        Err(err) => Err(error::Error::Other("Error find tx events for your transaction".to_string())),
-                    }
-                }
+                RPCRequestType::QueryTxEvents(tx_hash) => match find_tx_event(&client, &tx_hash).await {
+                    Ok(Some((_, event))) => Ok(RPCResult::TxEvents(event)),
+                    Ok(None) => Err(error::Error::Other("Unable to find tx events for your transaction.".to_string())),
+                    Err(_) => Err(error::Error::Other("Error to find tx events for your transaction.".to_string())),
+                },
+                RPCRequestType::QueryTxEventStage(tx_hash) => find_tx_event(&client, &tx_hash)
+                    .await
+                    .map(RPCResult::TxEventStage),
                 RPCRequestType::QueryNativeToken => rpc::query_native_token(&client).await.map(RPCResult::NativeToken),
                 RPCRequestType::QueryLatestBlock => rpc::query_block(&client).await.map(RPCResult::LatestBlock),
                 RPCRequestType::QueryCheckIsValidator(address) => rpc::is_validator(&client, &address)
@@ -415,6 +919,79 @@ pub async fn get_rpc_data(
                 RPCRequestType::QueryValidatorStaked(epoch, address) => rpc::get_validator_stake(&client, epoch, &address)
                     .await
                     .map(RPCResult::TotalStakedTokens),
+                RPCRequestType::QueryRewards(source, validator) => rpc::query_rewards(&client, &source, &validator)
+                    .await
+                    .map(RPCResult::Rewards),
+                RPCRequestType::QueryBlockResults(height) => client.block_results(height.0 as u32)
+                    .await
+                    .map(|results| {
+                        // Namada emits its tx events (applied, accepted, ...) at the end of the block
+                        let events = results.end_block_events.unwrap_or_default().into_iter().map(|event| {
+                            EventSerializable {
+                                event_type: event.kind,
+                                level: "Block".to_string(),
+                                attributes: event.attributes.into_iter().map(|attr| (attr.key, attr.value)).collect(),
+                            }
+                        }).collect();
+                        RPCResult::BlockResults(height, events)
+                    })
+                    .map_err(|err| error::Error::Other(err.to_string())),
+                RPCRequestType::QueryBlock(height) => client.block(height.0 as u32)
+                    .await
+                    .map(|response| RPCResult::Block(RawBlock {
+                        height,
+                        hash: response.block_id.hash.to_string(),
+                        time: response.block.header.time.to_rfc3339(),
+                        txs: response.block.data,
+                    }))
+                    .map_err(|err| error::Error::Other(err.to_string())),
+                RPCRequestType::QueryAccountInfo(address) => rpc::get_account_info(&client, &address)
+                    .await
+                    .map(RPCResult::AccountInfo),
+                RPCRequestType::QueryBondsAndUnbonds(source, validator) => rpc::bonds_and_unbonds(&client, &source, &validator)
+                    .await
+                    .map(RPCResult::BondsAndUnbonds),
+                // Only established accounts store their own VP, the others run a built-in one
+                RPCRequestType::QueryVpCodeHash(address) => match address {
+                    Address::Established(_) => rpc::query_storage_value::<_, Hash>(&client, &Key::validity_predicate(&address))
+                        .await
+                        .map(|hash| RPCResult::VpCodeHash(Some(hash))),
+                    _ => Ok(RPCResult::VpCodeHash(None)),
+                },
+                RPCRequestType::QueryMissedVotes(address) => query_missed_votes(&client, &address)
+                    .await
+                    .map(RPCResult::MissedVotes),
+                RPCRequestType::QueryStorageValue(key) => rpc::query_storage_value_bytes(&client, &key, None, false)
+                    .await
+                    .map(|(bytes, _)| RPCResult::StorageValue(bytes)),
+                RPCRequestType::QueryConversions => rpc::query_conversions(&client)
+                    .await
+                    .map(RPCResult::Conversions),
+                RPCRequestType::QueryStoragePrefix(key) => RPC.shell().storage_prefix(&client, None, None, false, &key)
+                    .await
+                    .map(|response| RPCResult::StoragePrefix(
+                        response.data.into_iter().map(|entry| (entry.key, entry.value)).collect(),
+                    ))
+                    .map_err(|err| error::Error::Other(err.to_string())),
+                RPCRequestType::QueryBridgePool => RPC.shell().eth_bridge().read_ethereum_bridge_pool(&client)
+                    .await
+                    .map(RPCResult::BridgePool)
+                    .map_err(|err| error::Error::Other(err.to_string())),
+                RPCRequestType::QueryBridgeContracts => async {
+                    let bridge = RPC.shell().eth_bridge().read_bridge_contract(&client).await?;
+                    let native_erc20 = RPC.shell().eth_bridge().read_native_erc20_contract(&client).await?;
+                    Ok(RPCResult::BridgeContracts(bridge.address, native_erc20))
+                }
+                    .await
+                    .map_err(|err: C::Error| error::Error::Other(err.to_string())),
+                RPCRequestType::QueryErc20FlowControl(erc20) => RPC.shell().eth_bridge().get_erc20_flow_control(&client, &erc20)
+                    .await
+                    .map(|flow| RPCResult::Erc20FlowControl(flow.whitelisted, flow.supply, flow.cap))
+                    .map_err(|err| error::Error::Other(err.to_string())),
+                RPCRequestType::QueryDryRun(tx_bytes) => RPC.shell().dry_run_tx(&client, Some(tx_bytes), None, false)
+                    .await
+                    .map(|response| RPCResult::DryRun(whole_gas_units(u64::from(response.data.gas_used))))
+                    .map_err(|err| error::Error::Other(err.to_string())),
             }
         })
     })
@@ -433,132 +1010,187 @@ pub async fn get_rpc_data(
             }
         })?;
 
-    result
-        .map(|rpc_result| {
-            match rpc_result {
-                RPCResult::Epoch(epoch_data) => Json(json!({ "epoch": epoch_data })),
-                RPCResult::EpochAtHeight(maybe_epoch) => match maybe_epoch {
-                    Some(epoch_data) => Json(json!({ "epoch": epoch_data })),
-                    None => Json(json!({ "epoch": "None" })),
-                },
-                RPCResult::ProposalResult(proposal_result) => {
-                    // We need to reformat proposal result data because it doesn't implement serialize
-                    if let Some(proposal_result) = proposal_result {
-                        let threshold = match proposal_result.tally_type {
-                            TallyType::TwoThirds => {
-                                proposal_result.total_voting_power.mul_ceil(Dec::two() / 3)
-                            }
-                            _ => proposal_result.total_voting_power.mul_ceil(Dec::one() / 3),
-                        };
-
-                        let thresh_frac =
-                            Dec::from(threshold) / Dec::from(proposal_result.total_voting_power);
-
-                        return Json(json!({
-                            "result": format!("{}", proposal_result.result),
-                            "total_voting_power": proposal_result.total_voting_power,
-                            "total_yay_power": proposal_result.total_yay_power,
-                            "total_nay_power": proposal_result.total_nay_power,
-                            "total_abstain_power": proposal_result.total_abstain_power,
-                            "threshold": threshold,
-                            "thresh_frac": thresh_frac
-                        }));
-                    }
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(_) => tracing::info!(rpc = %request, elapsed_ms, "upstream rpc call succeeded"),
+        Err(err) => tracing::warn!(rpc = %request, elapsed_ms, error = %err, "upstream rpc call failed"),
+    }
 
-                    return Json(json!({"error": "proposal not found"}));
-                }
-                RPCResult::ProposalVotes(votes) => {
-                    let wrapped = votes.into_iter().map(|vote| {
-                        VoteWrapper {
-                            validator: format!("{}", vote.validator),
-                            delegator: format!("{}", vote.delegator),
-                            data: format!("{}", vote.data),
-                        }
-                    }).collect::<Vec<_>>();
-                    Json(json!({ "data": wrapped }))
-                }
-                RPCResult::BalanceResult(amount) => Json(json!({ "balance": amount })),
-                RPCResult::ValidatorState(maybe_validator_state) => match maybe_validator_state {
-                    Some(validator_state) => {
-                        match validator_state {
-                            ValidatorState::Consensus => { Json(json!({ "state": "Consensus" })) }
-                            ValidatorState::BelowCapacity => { Json(json!({ "state": "BelowCapacity" })) }
-                            ValidatorState::BelowThreshold => { Json(json!({ "state": "BelowThreshold" })) }
-                            ValidatorState::Inactive => { Json(json!({ "state": "Inactive" })) }
-                            ValidatorState::Jailed => { Json(json!({ "state": "Jailed" })) }
-                        }
-                    }
-                    None => {
-                        Json(json!({ "state": "Your validator is either not a validator, \
-                        or an epoch before the current epoch has been queried (and the validator state information is no longer stored)" }))
-                    }
-                },
-                RPCResult::DelegatorDelegation(delegating) => Json(json!({ "data": delegating })),
-                RPCResult::DelegatorDelegationAt(delegating) => Json(json!({ "data": delegating })),
-                RPCResult::MetaData((meta_data, commission)) => {
-                    let meta_data = meta_data.map_or(json!(null), |data| json!(data));
-                    let commission = commission.map_or(json!(null), |comm| json!(CommissionPairWrapper {
-                        commission_rate: format!("{}", comm.commission_rate),
-                        max_commission_change_per_epoch: format!("{}", comm.max_commission_change_per_epoch)
-                    }));
-                    Json(json!({
-                        "metadata": meta_data,
-                        "commission": commission
-                    }))
-                }
-                RPCResult::GovernanceParameters(governance) => {
-                    let wrapped = GovernanceParametersWrapper {
-                        min_proposal_fund: format!("{}", governance.min_proposal_fund),
-                        max_proposal_code_size: format!("{}", governance.max_proposal_code_size),
-                        min_proposal_voting_period: format!("{}", governance.min_proposal_voting_period),
-                        max_proposal_period: format!("{}", governance.max_proposal_period),
-                        max_proposal_content_size: format!("{}", governance.max_proposal_content_size),
-                        min_proposal_grace_epochs: format!("{}", governance.min_proposal_grace_epochs),
-                    };
-                    Json(json!({ "data": wrapped }))
-                }
-                RPCResult::PosParameters(pos) => {
-                    let serialized_pos_params = convert_to_serializable_pos(pos);
-                    let json_pos_params = serde_json::to_value(&serialized_pos_params).unwrap();
-                    Json(json!({ "data": json_pos_params }))
-                }
-                RPCResult::IsSteward(result) => Json(json!({ "data": result })),
-                RPCResult::ValidatorConsensusKeys(result) => {
-                    Json(json!({ "data": serialize(&result, serde_json::value::Serializer).unwrap() }))
-                }
-                RPCResult::TxEvents(event) => {
-                    let serializable_event = to_serializable(event);
-                    Json(json!({ "data": serializable_event }))
-                }
-                RPCResult::NativeToken(token) => Json(json!({ "address": token })),
-                RPCResult::LatestBlock(last_block) => match last_block {
-                    Some(last_block) => {
-                        let serializable_block = block_to_serializable(&last_block);
-                        let json_last_block = serde_json::to_value(&serializable_block).unwrap();
-                        Json(json!({ "data": json_last_block }))
-                    }
-                    None => {
-                        Json(json!({ "data": "Error to query latest block" }))
+    result.map_err(MyErrorWrapper)
+}
+
+fn render_rpc_result(rpc_result: RPCResult) -> Json<Value> {
+    match rpc_result {
+        RPCResult::Epoch(epoch_data) => Json(json!({ "epoch": epoch_data })),
+        RPCResult::EpochAtHeight(maybe_epoch) => match maybe_epoch {
+            Some(epoch_data) => Json(json!({ "epoch": epoch_data })),
+            None => Json(json!({ "epoch": "None" })),
+        },
+        RPCResult::ProposalResult(proposal_result) => {
+            // We need to reformat proposal result data because it doesn't implement serialize
+            if let Some(proposal_result) = proposal_result {
+                let threshold = match proposal_result.tally_type {
+                    TallyType::TwoThirds => {
+                        proposal_result.total_voting_power.mul_ceil(Dec::two() / 3)
                     }
-                },
-                RPCResult::IsValidator(is_validator) => Json(json!({ "data": is_validator })),
-                RPCResult::IsDelegator(is_delegator) => Json(json!({ "data": is_delegator })),
-                RPCResult::MapsReward(rewards) => {
-                    let wrapped = rewards.into_iter().map(|masp_reward| {
-                        MaspTokenRewardDataWrapper {
-                            name: masp_reward.name,
-                            address: masp_reward.address,
-                            max_reward_rate: masp_reward.max_reward_rate,
-                            kp_gain: masp_reward.kp_gain,
-                            kd_gain: masp_reward.kd_gain,
-                            locked_amount_target: masp_reward.locked_amount_target,
-                        }
-                    }).collect::<Vec<_>>();
-                    Json(json!({ "data": wrapped }))
+                    _ => proposal_result.total_voting_power.mul_ceil(Dec::one() / 3),
+                };
+
+                let thresh_frac =
+                    Dec::from(threshold) / Dec::from(proposal_result.total_voting_power);
+
+                return Json(json!({
+                    "result": format!("{}", proposal_result.result),
+                    "total_voting_power": proposal_result.total_voting_power,
+                    "total_yay_power": proposal_result.total_yay_power,
+                    "total_nay_power": proposal_result.total_nay_power,
+                    "total_abstain_power": proposal_result.total_abstain_power,
+                    "threshold": threshold,
+                    "thresh_frac": thresh_frac
+                }));
+            }
+
+            return Json(json!({"error": "proposal not found"}));
+        }
+        RPCResult::ProposalVotes(votes) => {
+            let wrapped = votes.into_iter().map(|vote| {
+                VoteWrapper {
+                    validator: format!("{}", vote.validator),
+                    delegator: format!("{}", vote.delegator),
+                    data: format!("{}", vote.data),
                 }
-                RPCResult::TotalStakedTokens(amount) => Json(json!({ "total": amount })),
-                RPCResult::ValidatorStake(amount) => Json(json!({ "total": amount }))
+            }).collect::<Vec<_>>();
+            Json(json!({ "data": wrapped }))
+        }
+        RPCResult::BalanceResult(amount) => Json(json!({ "balance": amount })),
+        RPCResult::ValidatorState(maybe_validator_state) => match maybe_validator_state {
+            Some(validator_state) => Json(json!({ "state": validator_state_name(&validator_state) })),
+            None => {
+                Json(json!({ "state": "Your validator is either not a validator, \
+                or an epoch before the current epoch has been queried (and the validator state information is no longer stored)" }))
             }
-        })
-        .map_err(MyErrorWrapper)
+        },
+        RPCResult::DelegatorDelegation(delegating) => Json(json!({ "data": delegating })),
+        RPCResult::DelegatorDelegationAt(delegating) => Json(json!({ "data": delegating })),
+        RPCResult::MetaData((meta_data, commission)) => {
+            let meta_data = meta_data.map_or(json!(null), |data| json!(data));
+            let commission = commission.map_or(json!(null), |comm| json!(CommissionPairWrapper::from(comm)));
+            Json(json!({
+                "metadata": meta_data,
+                "commission": commission
+            }))
+        }
+        RPCResult::GovernanceParameters(governance) => {
+            let wrapped = GovernanceParametersWrapper {
+                min_proposal_fund: format!("{}", governance.min_proposal_fund),
+                max_proposal_code_size: format!("{}", governance.max_proposal_code_size),
+                min_proposal_voting_period: format!("{}", governance.min_proposal_voting_period),
+                max_proposal_period: format!("{}", governance.max_proposal_period),
+                max_proposal_content_size: format!("{}", governance.max_proposal_content_size),
+                min_proposal_grace_epochs: format!("{}", governance.min_proposal_grace_epochs),
+            };
+            Json(json!({ "data": wrapped }))
+        }
+        RPCResult::PosParameters(pos) => {
+            let serialized_pos_params = convert_to_serializable_pos(pos);
+            let json_pos_params = serde_json::to_value(&serialized_pos_params).unwrap();
+            Json(json!({ "data": json_pos_params }))
+        }
+        RPCResult::IsSteward(result) => Json(json!({ "data": result })),
+        RPCResult::ValidatorConsensusKeys(result) => {
+            Json(json!({ "data": serialize(&result, serde_json::value::Serializer).unwrap() }))
+        }
+        RPCResult::TxEvents(event) => {
+            let serializable_event = to_serializable(event);
+            Json(json!({ "data": serializable_event }))
+        }
+        RPCResult::NativeToken(token) => Json(json!({ "address": token })),
+        RPCResult::LatestBlock(last_block) => match last_block {
+            Some(last_block) => {
+                let serializable_block = block_to_serializable(&last_block);
+                let json_last_block = serde_json::to_value(&serializable_block).unwrap();
+                Json(json!({ "data": json_last_block }))
+            }
+            None => {
+                Json(json!({ "data": "Error to query latest block" }))
+            }
+        },
+        RPCResult::IsValidator(is_validator) => Json(json!({ "data": is_validator })),
+        RPCResult::IsDelegator(is_delegator) => Json(json!({ "data": is_delegator })),
+        RPCResult::MapsReward(rewards) => {
+            let wrapped = rewards.into_iter().map(|masp_reward| {
+                MaspTokenRewardDataWrapper {
+                    name: masp_reward.name,
+                    address: masp_reward.address,
+                    max_reward_rate: masp_reward.max_reward_rate,
+                    kp_gain: masp_reward.kp_gain,
+                    kd_gain: masp_reward.kd_gain,
+                    locked_amount_target: masp_reward.locked_amount_target,
+                }
+            }).collect::<Vec<_>>();
+            Json(json!({ "data": wrapped }))
+        }
+        RPCResult::TotalStakedTokens(amount) => Json(json!({ "total": amount })),
+        RPCResult::ValidatorStake(amount) => Json(json!({ "total": amount })),
+        RPCResult::Rewards(amount) => Json(json!({ "rewards": amount })),
+        RPCResult::BlockResults(height, events) => Json(json!({ "height": height, "data": events })),
+        RPCResult::Block(block) => Json(json!({
+            "height": block.height,
+            "hash": block.hash,
+            "time": block.time,
+            "num_txs": block.txs.len(),
+        })),
+        RPCResult::TxEventStage(found) => match found {
+            Some((stage, event)) => Json(json!({ "status": stage.as_str(), "data": to_serializable(event) })),
+            None => Json(json!({ "status": "pending" })),
+        },
+        RPCResult::AccountInfo(account) => match account {
+            Some(account) => Json(json!({ "data": {
+                "threshold": account.threshold,
+                "public_keys": public_keys_by_index(&account),
+            }})),
+            None => Json(json!({ "data": null })),
+        },
+        RPCResult::BondsAndUnbonds(details) => {
+            let data = details.into_iter().map(|(bond_id, detail)| json!({
+                "source": bond_id.source,
+                "validator": bond_id.validator,
+                "bonds": detail.bonds.iter().map(|bond| json!({
+                    "start": bond.start,
+                    "amount": bond.amount,
+                    "slashed_amount": bond.slashed_amount,
+                })).collect::<Vec<_>>(),
+                "unbonds": detail.unbonds.iter().map(|unbond| json!({
+                    "start": unbond.start,
+                    "withdraw": unbond.withdraw,
+                    "amount": unbond.amount,
+                    "slashed_amount": unbond.slashed_amount,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>();
+            Json(json!({ "data": data }))
+        }
+        RPCResult::VpCodeHash(hash) => Json(json!({ "data": hash.map(|hash| hash.to_string()) })),
+        RPCResult::MissedVotes(missed) => Json(json!({ "data": missed })),
+        RPCResult::StorageValue(bytes) => Json(json!({
+            "data": bytes.map(|bytes| bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<String>()),
+        })),
+        RPCResult::StoragePrefix(entries) => Json(json!({
+            "data": entries.into_iter().map(|(key, value)| json!({
+                "key": key.to_string(),
+                "value": value.iter().map(|byte| format!("{:02X}", byte)).collect::<String>(),
+            })).collect::<Vec<_>>(),
+        })),
+        RPCResult::BridgePool(transfers) => Json(json!({ "data": transfers })),
+        RPCResult::BridgeContracts(bridge, native_erc20) => Json(json!({
+            "bridge": bridge.to_canonical(),
+            "native_erc20": native_erc20.to_canonical(),
+        })),
+        RPCResult::Erc20FlowControl(whitelisted, supply, cap) => Json(json!({
+            "whitelisted": whitelisted,
+            "supply": supply,
+            "cap": cap,
+        })),
+        RPCResult::DryRun(gas_used) => Json(json!({ "gas_used": gas_used })),
+        RPCResult::Conversions(conversions) => Json(json!({ "data": conversions_to_json(&conversions, None) })),
+    }
 }
@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use namada_sdk::error;
+use namada_sdk::io::StdIo;
+use namada_sdk::masp::fs::FsShieldedUtils;
+use namada_sdk::masp::ShieldedContext;
+use namada_sdk::masp_primitives::zip32::ExtendedFullViewingKey;
+use namada_sdk::rpc;
+use namada_sdk::types::masp::ExtendedViewingKey;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+use crate::client::RpcClient;
+use crate::openapi::{ErrorResponse, ShieldedBalanceResponse};
+use crate::query::MyErrorWrapper;
+use crate::ServerState;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShieldedSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    // Where the synced notes are kept between requests; holds data derived from the viewing keys
+    #[serde(default = "default_cache_dir")]
+    pub cache_dir: String,
+}
+
+impl Default for ShieldedSettings {
+    fn default() -> Self {
+        ShieldedSettings { enabled: false, cache_dir: default_cache_dir() }
+    }
+}
+
+fn default_cache_dir() -> String {
+    "data/shielded".to_string()
+}
+
+// Syncs share one cache directory, so run them one at a time and turn away requests that would queue behind one
+static SYNC_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Deserialize, ToSchema)]
+pub struct ShieldedBalanceRequest {
+    /// Extended viewing key, e.g. zvknam1...
+    viewing_key: String,
+}
+
+fn other(message: impl std::fmt::Display) -> MyErrorWrapper {
+    MyErrorWrapper(error::Error::Other(message.to_string()))
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/shielded/balance",
+    request_body = ShieldedBalanceRequest,
+    responses(
+        (status = 200, description = "Sync the shielded notes a viewing key can see and return its balance per token.", body = ShieldedBalanceResponse),
+        (status = 400, description = "Invalid viewing key", body = ErrorResponse),
+        (status = 404, description = "Shielded queries are disabled on this server", body = ErrorResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+        (status = 503, description = "Another sync is in progress, retry later", body = ErrorResponse),
+    )
+)]
+pub async fn post_shielded_balance<C: RpcClient>(State(state): State<ServerState<C>>,
+                                                 Json(request): Json<ShieldedBalanceRequest>) -> Result<Json<Value>, Response> {
+    if !state.config.shielded.enabled {
+        return Err(error_response(
+            StatusCode::NOT_FOUND,
+            "Shielded queries are disabled on this server, enable them under [shielded] in the settings.",
+        ));
+    }
+    let viewing_key = ExtendedViewingKey::from_str(request.viewing_key.trim())
+        .map_err(|_| error_response(StatusCode::BAD_REQUEST, "Invalid viewing key."))?;
+    let sync = SYNC_LOCK
+        .try_lock()
+        .map_err(|_| error_response(StatusCode::SERVICE_UNAVAILABLE, "Shielded sync in progress, retry later."))?;
+    let vk = ExtendedFullViewingKey::from(viewing_key).fvk.vk;
+    let cache_dir = PathBuf::from(&state.config.shielded.cache_dir);
+    let client = state.client;
+
+    // ShieldedContext is !Send, like the rpc helpers, so it gets the same blocking-thread treatment
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _entered = span.enter();
+        let _sync = sync;
+        tokio::runtime::Handle::current().block_on(async {
+            std::fs::create_dir_all(&cache_dir).map_err(other)?;
+            let mut shielded: ShieldedContext<FsShieldedUtils> = FsShieldedUtils::new(cache_dir);
+            // A missing or stale cache only means a longer sync
+            let _ = shielded.load().await;
+            shielded
+                .fetch(&client, &namada_sdk::masp::DefaultLogger::new(&StdIo), None, 1, &[], &[vk])
+                .await
+                .map_err(MyErrorWrapper)?;
+            shielded.save().await.map_err(other)?;
+
+            let epoch = rpc::query_epoch(&client).await.map_err(MyErrorWrapper)?;
+            let balance = shielded
+                .compute_exchanged_balance(&client, &StdIo, &vk, epoch)
+                .await
+                .map_err(MyErrorWrapper)?
+                .unwrap_or_default();
+            let decoded = shielded.decode_combine_sum(&client, balance).await;
+
+            let balances = decoded
+                .components()
+                .map(|((_, token), amount)| json!({ "token": token, "amount": amount.to_string() }))
+                .collect::<Vec<_>>();
+            Ok::<_, MyErrorWrapper>(Json(json!({ "epoch": epoch, "data": balances })))
+        })
+    })
+    .await
+    .map_err(|err| other(format!("Shielded sync failed: {}", err)).into_response())?
+    .map_err(IntoResponse::into_response)
+}
@@ -0,0 +1,127 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
+use futures::stream::{self, Stream};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::client::RpcClient;
+use crate::query::{query_rpc, to_serializable, RPCRequestType, RPCResult, TxEventStage};
+use crate::ServerState;
+
+#[derive(Deserialize)]
+pub struct StreamParams {
+    /// Seconds to wait for the Applied event, capped at `tx_stream_timeout_secs`
+    timeout: Option<u64>,
+}
+
+/// Polls the two-stage tx event lookup and reports each status change once:
+/// pending -> accepted -> applied, or timeout if the tx never gets applied, or shutdown if the
+/// server stops first.
+struct TxWatch<C> {
+    client: C,
+    tx_hash: String,
+    poll_interval: Duration,
+    deadline: Instant,
+    shutdown: watch::Receiver<bool>,
+    last_status: Option<&'static str>,
+    finished: bool,
+}
+
+impl<C: RpcClient> TxWatch<C> {
+    async fn next_event(&mut self) -> Option<SseEvent> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            if self.last_status.is_some() {
+                tokio::select! {
+                    _ = tokio::time::sleep(self.poll_interval) => {}
+                    _ = shutting_down(self.shutdown.clone()) => {}
+                }
+            }
+            if *self.shutdown.borrow() {
+                return Some(self.finish("shutdown"));
+            }
+            if Instant::now() >= self.deadline {
+                return Some(self.finish("timeout"));
+            }
+
+            let req_type = RPCRequestType::QueryTxEventStage(self.tx_hash.clone());
+            let (status, body) = match query_rpc(self.client.clone(), req_type).await {
+                Ok(RPCResult::TxEventStage(Some((stage, event)))) => (
+                    stage.as_str(),
+                    json!({ "status": stage.as_str(), "tx_hash": self.tx_hash, "data": to_serializable(event) }),
+                ),
+                Ok(_) => ("pending", json!({ "status": "pending", "tx_hash": self.tx_hash })),
+                // Transient node errors are reported but don't end the stream
+                Err(err) => ("error", json!({ "status": "error", "tx_hash": self.tx_hash, "error": err.to_string() })),
+            };
+
+            if status == TxEventStage::Applied.as_str() {
+                self.finished = true;
+            }
+            if self.last_status != Some(status) {
+                self.last_status = Some(status);
+                return Some(status_event(status, body));
+            }
+        }
+    }
+
+    fn finish(&mut self, status: &str) -> SseEvent {
+        self.finished = true;
+        status_event(status, json!({ "status": status, "tx_hash": self.tx_hash }))
+    }
+}
+
+async fn shutting_down(mut shutdown: watch::Receiver<bool>) {
+    // A dropped sender means no shutdown was wired up, so there is nothing to wait for
+    if shutdown.wait_for(|stopping| *stopping).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+fn status_event(status: &str, body: Value) -> SseEvent {
+    SseEvent::default().event(status).data(body.to_string())
+}
+
+#[utoipa::path(
+    get,
+    path = "/tx_event/{tx_hash}/stream",
+    params(
+        ("tx_hash" = String, Path, description = "Hex-encoded tx hash"),
+        ("timeout" = Option<u64>, Query, description = "Seconds to wait for the tx to be applied, at most tx_stream_timeout_secs"),
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events stream of the tx's status: its current status (pending, accepted or applied) first, then each change, ending with applied, timeout or shutdown. Each event's data carries `status`, `tx_hash` and, once accepted or applied, the `/tx_event` data; node errors arrive as `error` events.", content_type = "text/event-stream", body = String),
+    )
+)]
+pub async fn stream_tx_event<C: RpcClient>(
+    State(state): State<ServerState<C>>,
+    Path(tx_hash): Path<String>,
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = Result<SseEvent, Infallible>>> {
+    let timeout = params
+        .timeout
+        .unwrap_or(state.config.tx_stream_timeout_secs)
+        .min(state.config.tx_stream_timeout_secs);
+    let watch = TxWatch {
+        client: state.client,
+        tx_hash,
+        poll_interval: Duration::from_millis(state.config.tx_stream_poll_interval_ms),
+        deadline: Instant::now() + Duration::from_secs(timeout),
+        shutdown: state.shutdown,
+        last_status: None,
+        finished: false,
+    };
+
+    let events = stream::unfold(watch, |mut watch| async move {
+        watch.next_event().await.map(|event| (Ok::<_, Infallible>(event), watch))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
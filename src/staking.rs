@@ -0,0 +1,132 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use namada_sdk::parameters::storage::get_epochs_per_year_key;
+use namada_sdk::proof_of_stake::storage_key::last_pos_inflation_amount_key;
+use namada_sdk::proof_of_stake::PosParams;
+use namada_sdk::state::Epoch;
+use namada_sdk::token::storage_key::minted_balance_key;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::token;
+use serde_json::{json, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{ErrorResponse, StakingAprResponse, ValidatorAprResponse};
+use crate::query::{
+    current_epoch, dec_to_f64, native_token, pos_params, query_as, query_storage, ratio, to_f64,
+    validator_metadata, validator_stake, MyErrorWrapper, RPCRequestType, RPCResult,
+};
+use crate::ServerState;
+
+const APR_FORMULA: &str = "apr = last_inflation * epochs_per_year / total_staked; \
+    max_apr = max_inflation_rate * total_supply / total_staked; \
+    validator_apr = apr * (1 - commission_rate). \
+    last_inflation is the PoS inflation minted in the last epoch, so apr extrapolates the current reward rate \
+    over a year without compounding and ignores the small proposer and signer bonuses.";
+
+/// Chain-wide figures every APR estimate starts from
+struct StakingInputs {
+    epoch: Epoch,
+    params: PosParams,
+    epochs_per_year: u64,
+    last_inflation: token::Amount,
+    total_supply: token::Amount,
+    total_staked: token::Amount,
+}
+
+impl StakingInputs {
+    fn apr(&self) -> f64 {
+        ratio(to_f64(self.last_inflation) * self.epochs_per_year as f64, to_f64(self.total_staked))
+    }
+
+    fn max_apr(&self) -> f64 {
+        ratio(dec_to_f64(self.params.owned.max_inflation_rate) * to_f64(self.total_supply), to_f64(self.total_staked))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "epoch": self.epoch,
+            "epochs_per_year": self.epochs_per_year,
+            "last_inflation": self.last_inflation,
+            "total_supply": self.total_supply,
+            "total_staked": self.total_staked,
+            "staked_ratio": ratio(to_f64(self.total_staked), to_f64(self.total_supply)),
+            "max_inflation_rate": self.params.owned.max_inflation_rate.to_string(),
+            "target_staked_ratio": self.params.owned.target_staked_ratio.to_string(),
+        })
+    }
+}
+
+async fn staking_inputs<C: RpcClient>(client: &C) -> Result<StakingInputs, MyErrorWrapper> {
+    let (epoch, params, native_token, epochs_per_year, last_inflation) = futures::try_join!(
+        current_epoch(client),
+        pos_params(client),
+        native_token(client),
+        query_storage::<_, u64>(client, get_epochs_per_year_key()),
+        query_storage::<_, token::Amount>(client, last_pos_inflation_amount_key()),
+    )?;
+    let (total_supply, total_staked) = futures::try_join!(
+        query_storage::<_, token::Amount>(client, minted_balance_key(&native_token)),
+        query_as(client, RPCRequestType::QueryTotalStakedTokens(epoch), |result| match result {
+            RPCResult::TotalStakedTokens(amount) => Some(amount),
+            _ => None,
+        }),
+    )?;
+
+    Ok(StakingInputs {
+        epoch,
+        params,
+        epochs_per_year: epochs_per_year.unwrap_or_default(),
+        last_inflation: last_inflation.unwrap_or_default(),
+        total_supply: total_supply.unwrap_or_default(),
+        total_staked,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/staking/apr",
+    responses(
+        (status = 200, description = "Estimated staking APR before commission, with the inputs and formula behind it.", body = StakingAprResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_staking_apr<C: RpcClient>(State(state): State<ServerState<C>>) -> Result<Json<Value>, MyErrorWrapper> {
+    let inputs = staking_inputs(&state.client).await?;
+    Ok(Json(json!({
+        "apr": inputs.apr(),
+        "max_apr": inputs.max_apr(),
+        "inputs": inputs.to_json(),
+        "formula": APR_FORMULA,
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/staking/apr/{address}",
+    params(("address" = String, Path, description = "Validator address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Estimated APR for delegators of a validator, net of its current commission.", body = ValidatorAprResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_apr<C: RpcClient>(State(state): State<ServerState<C>>,
+                                             Path(address): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let inputs = staking_inputs(client).await?;
+    let ((_, commission), stake) = futures::try_join!(
+        validator_metadata(client, &address, inputs.epoch),
+        validator_stake(client, &address, inputs.epoch),
+    )?;
+    let commission_rate = commission.map_or(0.0, |commission| dec_to_f64(commission.commission_rate));
+    let apr = inputs.apr();
+
+    Ok(Json(json!({
+        "address": address,
+        "commission_rate": commission_rate,
+        "stake": stake,
+        "apr": apr * (1.0 - commission_rate),
+        "gross_apr": apr,
+        "inputs": inputs.to_json(),
+        "formula": APR_FORMULA,
+    })))
+}
@@ -0,0 +1,55 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use namada_sdk::proof_of_stake::storage_key::last_pos_inflation_amount_key;
+use namada_sdk::token::storage_key::{masp_last_inflation_key, minted_balance_key};
+use namada_sdk::types::address::{Address, InternalAddress};
+use namada_sdk::types::token;
+use serde_json::{json, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{ErrorResponse, SupplyResponse};
+use crate::query::{balance_of, current_epoch, native_token, query_storage, MyErrorWrapper};
+use crate::ServerState;
+
+#[utoipa::path(
+    get,
+    path = "/supply/{token}",
+    params(("token" = String, Path, description = "Token address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "A token's minted supply, where it is locked, and the inflation minted for it last epoch.", body = SupplyResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_supply<C: RpcClient>(State(state): State<ServerState<C>>,
+                                      Path(token): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (epoch, native_token, total_supply, locked_in_pos, locked_in_masp, pgf_treasury, masp_inflation) = futures::try_join!(
+        current_epoch(client),
+        native_token(client),
+        query_storage::<_, token::Amount>(client, minted_balance_key(&token)),
+        balance_of(client, &token, Address::Internal(InternalAddress::PoS)),
+        balance_of(client, &token, Address::Internal(InternalAddress::Masp)),
+        balance_of(client, &token, Address::Internal(InternalAddress::Pgf)),
+        query_storage::<_, token::Amount>(client, masp_last_inflation_key(&token)),
+    )?;
+
+    // Only the native token is inflated for PoS rewards
+    let pos_inflation = if token == native_token {
+        query_storage::<_, token::Amount>(client, last_pos_inflation_amount_key()).await?
+    } else {
+        None
+    };
+
+    Ok(Json(json!({
+        "token": token,
+        "epoch": epoch,
+        "total_supply": total_supply.unwrap_or_default(),
+        "locked_in_pos": locked_in_pos,
+        "locked_in_masp": locked_in_masp,
+        "pgf_treasury": pgf_treasury,
+        "last_inflation": {
+            "pos": pos_inflation,
+            "masp": masp_inflation.unwrap_or_default(),
+        },
+    })))
+}
@@ -0,0 +1,453 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use namada_sdk::proof_of_stake::types::{BondsAndUnbondsDetail, ValidatorState};
+use namada_sdk::state::Epoch;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::dec::Dec;
+use namada_sdk::types::key::common;
+use namada_sdk::types::token;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::client::RpcClient;
+use crate::openapi::{
+    ErrorResponse, ValidatorCommissionResponse, ValidatorDelegatorsResponse, ValidatorLivenessResponse,
+    ValidatorResponse,
+};
+use crate::query::{
+    current_epoch, pos_params, query_as, validator_metadata, validator_stake, validator_state_name, AtEpoch,
+    CommissionPairWrapper, MyErrorWrapper, Page, RPCRequestType, RPCResult,
+};
+use crate::ServerState;
+
+// Series length when no range is given, and the most epochs one request may span
+const DEFAULT_SERIES_LEN: u64 = 10;
+const MAX_SERIES_LEN: u64 = 100;
+// Epochs of a series queried at once, kept apart from `batch_concurrency` so a long series
+// doesn't take its limit from a setting meant for /batch
+const SERIES_CONCURRENCY: usize = 8;
+
+#[derive(Deserialize)]
+pub struct EpochRange {
+    from_epoch: Option<u64>,
+    to_epoch: Option<u64>,
+}
+
+impl EpochRange {
+    /// Fill in missing bounds around `default_to` and clamp the range to `MAX_SERIES_LEN` epochs,
+    /// keeping the most recent end
+    pub(crate) fn resolve(&self, default_to: Epoch) -> (Epoch, Epoch) {
+        let to = self.to_epoch.unwrap_or(default_to.0);
+        let from = self
+            .from_epoch
+            .unwrap_or_else(|| to.saturating_sub(DEFAULT_SERIES_LEN - 1))
+            .min(to)
+            .max(to.saturating_sub(MAX_SERIES_LEN - 1));
+        (Epoch(from), Epoch(to))
+    }
+}
+
+async fn validator_state<C: RpcClient>(client: &C, address: &Address, epoch: Epoch) -> Result<Option<ValidatorState>, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryValidatorState(address.clone(), Some(epoch)), |result| match result {
+        RPCResult::ValidatorState(state) => Some(state),
+        _ => None,
+    })
+    .await
+}
+
+async fn consensus_key<C: RpcClient>(client: &C, address: &Address) -> Result<Option<common::PublicKey>, MyErrorWrapper> {
+    query_as(client, RPCRequestType::QueryValidatorConsensusKeys(address.clone()), |result| match result {
+        RPCResult::ValidatorConsensusKeys(key) => Some(key),
+        _ => None,
+    })
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/validator/{address}",
+    params(
+        ("address" = String, Path, description = "Validator address, e.g. tnam1q..."),
+        ("from_epoch" = Option<u64>, Query, description = "First epoch of the series, defaults to 9 epochs before to_epoch"),
+        ("to_epoch" = Option<u64>, Query, description = "Last epoch of the series, defaults to the current epoch"),
+    ),
+    responses(
+        (status = 200, description = "A validator's current profile and its stake, state and commission per epoch. The series covers at most 100 epochs.", body = ValidatorResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator<C: RpcClient>(State(state): State<ServerState<C>>,
+                                         Path(address): Path<Address>,
+                                         Query(range): Query<EpochRange>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let epoch = current_epoch(client).await?;
+    let (current_state, stake, (metadata, commission), consensus_key) = futures::try_join!(
+        validator_state(client, &address, epoch),
+        validator_stake(client, &address, epoch),
+        validator_metadata(client, &address, epoch),
+        consensus_key(client, &address),
+    )?;
+
+    let (from, to) = range.resolve(epoch);
+    let series = stream::iter(from.0..=to.0)
+        .map(|epoch| {
+            let address = &address;
+            async move {
+                let epoch = Epoch(epoch);
+                let (epoch_state, stake, (_, commission)) = futures::try_join!(
+                    validator_state(client, address, epoch),
+                    validator_stake(client, address, epoch),
+                    validator_metadata(client, address, epoch),
+                )?;
+                Ok::<_, MyErrorWrapper>(json!({
+                    "epoch": epoch,
+                    "state": epoch_state.as_ref().map(validator_state_name),
+                    "stake": stake,
+                    "commission": commission.map(CommissionPairWrapper::from),
+                }))
+            }
+        })
+        .buffered(SERIES_CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(Json(json!({
+        "address": address,
+        "epoch": epoch,
+        "state": current_state.as_ref().map(validator_state_name),
+        "stake": stake,
+        "commission": commission.map(CommissionPairWrapper::from),
+        "metadata": metadata,
+        "consensus_key": consensus_key.map(|key| key.to_string()),
+        "series": series,
+    })))
+}
+
+// The amount bonded at `epoch`, net of slashes: current bonds from their start epoch, plus
+// unbonds from their start epoch until their tokens left the bond
+fn bonded_at(detail: &BondsAndUnbondsDetail, epoch: Epoch, unbonding_offset: u64) -> token::Amount {
+    let net = |amount: token::Amount, slashed: Option<token::Amount>| {
+        amount.checked_sub(slashed.unwrap_or_default()).unwrap_or_default()
+    };
+    let bonded = detail
+        .bonds
+        .iter()
+        .filter(|bond| bond.start <= epoch)
+        .map(|bond| net(bond.amount, bond.slashed_amount));
+    let unbonded = detail
+        .unbonds
+        .iter()
+        .filter(|unbond| unbond.start <= epoch && epoch.0 < unbond.withdraw.0.saturating_sub(unbonding_offset))
+        .map(|unbond| net(unbond.amount, unbond.slashed_amount));
+    bonded.chain(unbonded).fold(token::Amount::zero(), |total, amount| total + amount)
+}
+
+#[utoipa::path(
+    get,
+    path = "/validator/{address}/delegators",
+    params(
+        ("address" = String, Path, description = "Validator address, e.g. tnam1q..."),
+        ("epoch" = Option<u64>, Query, description = "Epoch to report bonds at, defaults to the current epoch"),
+        ("limit" = Option<u64>, Query, description = "Page size, 50 by default and at most 500"),
+        ("offset" = Option<u64>, Query, description = "Delegators to skip"),
+    ),
+    responses(
+        (status = 200, description = "A validator's delegators and their bonded amounts at an epoch, largest first. Amounts at past epochs add back unbonds that were still bonded then; unbonds already withdrawn are no longer stored and are missing from them.", body = ValidatorDelegatorsResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_delegators<C: RpcClient>(State(state): State<ServerState<C>>,
+                                                    Path(address): Path<Address>,
+                                                    Query(at): Query<AtEpoch>,
+                                                    Query(page): Query<Page>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let epoch = at.resolve(client).await?;
+    let (params, bonds) = futures::try_join!(
+        pos_params(client),
+        query_as(client, RPCRequestType::QueryBondsAndUnbonds(None, Some(address.clone())), |result| match result {
+            RPCResult::BondsAndUnbonds(details) => Some(details),
+            _ => None,
+        }),
+    )?;
+
+    // An unbond's tokens leave the bond at the pipeline epoch of the unbonding, which its
+    // withdrawable epoch trails by the unbonding length and the cubic slashing window
+    let unbonding_offset = params.owned.unbonding_len + params.owned.cubic_slashing_window_length;
+    let mut delegators = bonds
+        .into_iter()
+        .filter_map(|(bond_id, detail)| {
+            let amount = bonded_at(&detail, epoch, unbonding_offset);
+            (!amount.is_zero()).then_some((bond_id.source, amount))
+        })
+        .collect::<Vec<_>>();
+    delegators.sort_by(|(a_source, a_amount), (b_source, b_amount)| {
+        b_amount.cmp(a_amount).then_with(|| a_source.cmp(b_source))
+    });
+
+    let total = delegators.len();
+    let (limit, offset) = (page.limit(), page.offset());
+    let data = delegators
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|(delegator, amount)| json!({
+            "delegator": delegator,
+            "amount": amount,
+            "self_bond": delegator == address,
+        }))
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({
+        "address": address,
+        "epoch": epoch,
+        "total": total,
+        "data": data,
+        "limit": limit,
+        "offset": offset,
+    })))
+}
+
+// PoS (`jail_for_liveness`) jails a validator once its missed votes reach
+// (1 - liveness_threshold) * liveness_window_check, rounded down. Returns that limit and how
+// many more votes can be missed while staying below it.
+fn liveness_limits(threshold: Dec, window: u64, missed_votes: u64) -> (u64, u64) {
+    let max_missed_votes = ((Dec::one() - threshold) * Dec::from(window))
+        .to_uint()
+        .map_or(0, |max| max.as_u64());
+    (max_missed_votes, max_missed_votes.saturating_sub(missed_votes.saturating_add(1)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/validator/{address}/liveness",
+    params(("address" = String, Path, description = "Validator address, e.g. tnam1q...")),
+    responses(
+        (status = 200, description = "Missed votes in the current liveness window and how many more the validator can miss before being jailed.", body = ValidatorLivenessResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_liveness<C: RpcClient>(State(state): State<ServerState<C>>,
+                                                  Path(address): Path<Address>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (params, missed_votes, current_state) = futures::try_join!(
+        pos_params(client),
+        query_as(client, RPCRequestType::QueryMissedVotes(address.clone()), |result| match result {
+            RPCResult::MissedVotes(missed) => Some(missed),
+            _ => None,
+        }),
+        async { validator_state(client, &address, current_epoch(client).await?).await },
+    )?;
+
+    let window = params.owned.liveness_window_check;
+    let threshold = params.owned.liveness_threshold;
+    let missed_votes = missed_votes.unwrap_or_default();
+    let (max_missed_votes, remaining_missed_votes) = liveness_limits(threshold, window, missed_votes);
+
+    Ok(Json(json!({
+        "address": address,
+        "state": current_state.as_ref().map(validator_state_name),
+        "missed_votes": missed_votes,
+        "liveness_window_check": window,
+        "liveness_threshold": threshold.to_string(),
+        "max_missed_votes": max_missed_votes,
+        "remaining_missed_votes": remaining_missed_votes,
+        "formula": "jailed when missed_votes >= (1 - liveness_threshold) * liveness_window_check",
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/validator/{address}/commission",
+    params(
+        ("address" = String, Path, description = "Validator address, e.g. tnam1q..."),
+        ("from_epoch" = Option<u64>, Query, description = "First epoch, defaults to 9 epochs before the current one"),
+        ("to_epoch" = Option<u64>, Query, description = "Last epoch, defaults to and is capped at the current epoch + pipeline_len"),
+    ),
+    responses(
+        (status = 200, description = "A validator's commission per epoch, including changes already scheduled for the coming pipeline_len epochs.", body = ValidatorCommissionResponse),
+        (status = 500, description = "Upstream RPC error", body = ErrorResponse),
+    )
+)]
+pub async fn get_validator_commission<C: RpcClient>(State(state): State<ServerState<C>>,
+                                                    Path(address): Path<Address>,
+                                                    Query(range): Query<EpochRange>) -> Result<Json<Value>, MyErrorWrapper> {
+    let client = &state.client;
+    let (epoch, params) = futures::try_join!(current_epoch(client), pos_params(client))?;
+
+    // Commission changes take effect `pipeline_len` epochs after they are submitted, so nothing
+    // further ahead is known yet
+    let last_known = Epoch(epoch.0 + params.owned.pipeline_len);
+    let (from, to) = EpochRange {
+        from_epoch: Some(range.from_epoch.unwrap_or_else(|| epoch.0.saturating_sub(DEFAULT_SERIES_LEN - 1))),
+        to_epoch: Some(range.to_epoch.unwrap_or(last_known.0).min(last_known.0)),
+    }
+    .resolve(last_known);
+
+    let rates = stream::iter(from.0..=to.0)
+        .map(|series_epoch| {
+            let address = &address;
+            async move {
+                let (_, commission) = validator_metadata(client, address, Epoch(series_epoch)).await?;
+                Ok::<_, MyErrorWrapper>((Epoch(series_epoch), commission))
+            }
+        })
+        .buffered(SERIES_CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let (changes, upcoming_increase) = commission_changes(
+        rates
+            .iter()
+            .map(|(rate_epoch, commission)| (*rate_epoch, commission.as_ref().map(|pair| pair.commission_rate))),
+        epoch,
+    );
+    let changes = changes
+        .into_iter()
+        .map(|change| json!({
+            "epoch": change.epoch,
+            "from": change.from.to_string(),
+            "to": change.to.to_string(),
+            "pending": change.epoch > epoch,
+        }))
+        .collect::<Vec<_>>();
+    let series = rates
+        .into_iter()
+        .map(|(rate_epoch, commission)| json!({
+            "epoch": rate_epoch,
+            "pending": rate_epoch > epoch,
+            "commission": commission.map(CommissionPairWrapper::from),
+        }))
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({
+        "address": address,
+        "epoch": epoch,
+        "pipeline_len": params.owned.pipeline_len,
+        "series": series,
+        "changes": changes,
+        "upcoming_increase": upcoming_increase,
+    })))
+}
+
+struct CommissionChange {
+    epoch: Epoch,
+    from: Dec,
+    to: Dec,
+}
+
+/// Rate changes across `rates` in epoch order, and whether one after `current` raises the rate
+/// above the one in effect at `current`. Epochs without commission data (e.g. before the
+/// validator existed) are skipped, so each change compares two known rates.
+fn commission_changes(
+    rates: impl IntoIterator<Item = (Epoch, Option<Dec>)>,
+    current: Epoch,
+) -> (Vec<CommissionChange>, bool) {
+    let mut changes = Vec::new();
+    let mut current_rate = None;
+    let mut previous_rate: Option<Dec> = None;
+    for (epoch, rate) in rates {
+        let Some(rate) = rate else { continue };
+        if epoch <= current {
+            current_rate = Some(rate);
+        }
+        if let Some(from) = previous_rate.filter(|previous| *previous != rate) {
+            changes.push(CommissionChange { epoch, from, to: rate });
+        }
+        previous_rate = Some(rate);
+    }
+    let upcoming_increase = current_rate.map_or(false, |current_rate| {
+        changes.iter().any(|change| change.epoch > current && change.to > current_rate)
+    });
+    (changes, upcoming_increase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(from_epoch: Option<u64>, to_epoch: Option<u64>) -> EpochRange {
+        EpochRange { from_epoch, to_epoch }
+    }
+
+    #[test]
+    fn epoch_range_defaults_to_the_last_ten_epochs() {
+        assert_eq!(range(None, None).resolve(Epoch(50)), (Epoch(41), Epoch(50)));
+        assert_eq!(range(None, Some(20)).resolve(Epoch(50)), (Epoch(11), Epoch(20)));
+        assert_eq!(range(Some(45), None).resolve(Epoch(50)), (Epoch(45), Epoch(50)));
+        // Near genesis the series starts at epoch 0
+        assert_eq!(range(None, None).resolve(Epoch(3)), (Epoch(0), Epoch(3)));
+    }
+
+    #[test]
+    fn epoch_range_with_from_after_to_is_a_single_epoch() {
+        assert_eq!(range(Some(20), Some(10)).resolve(Epoch(50)), (Epoch(10), Epoch(10)));
+    }
+
+    #[test]
+    fn epoch_range_is_clamped_to_the_most_recent_hundred_epochs() {
+        assert_eq!(range(Some(0), Some(500)).resolve(Epoch(50)), (Epoch(401), Epoch(500)));
+        assert_eq!(range(Some(0), None).resolve(Epoch(99)), (Epoch(0), Epoch(99)));
+        assert_eq!(range(Some(0), None).resolve(Epoch(100)), (Epoch(1), Epoch(100)));
+    }
+
+    fn dec(rate: &str) -> Dec {
+        rate.parse().unwrap()
+    }
+
+    #[test]
+    fn liveness_jails_on_reaching_the_missed_votes_limit() {
+        // 10% of a 100 block window
+        assert_eq!(liveness_limits(dec("0.9"), 100, 0), (10, 9));
+        assert_eq!(liveness_limits(dec("0.9"), 100, 8), (10, 1));
+        // One more miss reaches the limit
+        assert_eq!(liveness_limits(dec("0.9"), 100, 9), (10, 0));
+        assert_eq!(liveness_limits(dec("0.9"), 100, 10), (10, 0));
+        assert_eq!(liveness_limits(dec("0.9"), 100, 15), (10, 0));
+        // The limit rounds down
+        assert_eq!(liveness_limits(dec("0.9"), 105, 0), (10, 9));
+    }
+
+    fn rates(rates: &[(u64, Option<&str>)]) -> Vec<(Epoch, Option<Dec>)> {
+        rates.iter().map(|(epoch, rate)| (Epoch(*epoch), rate.map(dec))).collect()
+    }
+
+    fn change_epochs(changes: &[CommissionChange]) -> Vec<u64> {
+        changes.iter().map(|change| change.epoch.0).collect()
+    }
+
+    #[test]
+    fn commission_changes_skip_epochs_without_data() {
+        let (changes, upcoming_increase) =
+            commission_changes(rates(&[(8, None), (9, None), (10, Some("0.05")), (11, Some("0.05"))]), Epoch(11));
+        assert!(changes.is_empty());
+        assert!(!upcoming_increase);
+
+        // A gap doesn't hide a real change either
+        let (changes, _) = commission_changes(rates(&[(8, Some("0.05")), (9, None), (10, Some("0.1"))]), Epoch(10));
+        assert_eq!(change_epochs(&changes), vec![10]);
+        assert_eq!((changes[0].from, changes[0].to), (dec("0.05"), dec("0.1")));
+    }
+
+    #[test]
+    fn commission_changes_flag_only_scheduled_increases() {
+        // Past increase, scheduled decrease
+        let series = rates(&[(9, Some("0.05")), (10, Some("0.1")), (11, Some("0.1")), (12, Some("0.08"))]);
+        let (changes, upcoming_increase) = commission_changes(series, Epoch(11));
+        assert_eq!(change_epochs(&changes), vec![10, 12]);
+        assert!(!upcoming_increase);
+
+        // Scheduled increase
+        let (changes, upcoming_increase) =
+            commission_changes(rates(&[(10, Some("0.05")), (11, Some("0.05")), (12, Some("0.07"))]), Epoch(11));
+        assert_eq!(change_epochs(&changes), vec![12]);
+        assert!(upcoming_increase);
+    }
+
+    #[test]
+    fn commission_changes_need_a_current_rate_to_flag_an_increase() {
+        let (changes, upcoming_increase) = commission_changes(rates(&[(11, None), (12, Some("0.05"))]), Epoch(11));
+        assert!(changes.is_empty());
+        assert!(!upcoming_increase);
+    }
+}
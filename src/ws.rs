@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use axum::Extension;
+use namada_sdk::state::BlockHeight;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::client::RpcQuerier;
+use crate::query::{RPCRequestType, RPCResult};
+
+// How many missed heights the poller backfills tx events for after falling behind
+const MAX_BLOCK_CATCH_UP: u64 = 20;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChainEvent {
+    NewBlock { height: u64, hash: String, time: String },
+    NewEpoch { epoch: u64 },
+    Tx { height: u64, event_type: String, attributes: HashMap<String, String> },
+}
+
+/// Fans a single upstream polling loop out to every connected `/ws` client. The loop only
+/// starts with the first subscriber and skips its queries while nobody is connected.
+pub struct ChainEvents {
+    sender: broadcast::Sender<ChainEvent>,
+    querier: RpcQuerier,
+    poll_interval: Duration,
+    started: AtomicBool,
+}
+
+impl ChainEvents {
+    pub fn new(querier: RpcQuerier, poll_interval: Duration) -> Self {
+        let (sender, _) = broadcast::channel(1024);
+        ChainEvents { sender, querier, poll_interval, started: AtomicBool::new(false) }
+    }
+
+    fn subscribe(self: &Arc<Self>) -> broadcast::Receiver<ChainEvent> {
+        if !self.started.swap(true, Ordering::SeqCst) {
+            tokio::spawn(self.clone().poll());
+        }
+        self.sender.subscribe()
+    }
+
+    async fn poll(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.poll_interval);
+        let mut last_height: Option<u64> = None;
+        let mut last_epoch: Option<u64> = None;
+
+        loop {
+            interval.tick().await;
+            if self.sender.receiver_count() == 0 {
+                continue;
+            }
+
+            match self.querier.query(RPCRequestType::QueryLatestBlock).await {
+                Ok(RPCResult::LatestBlock(Some(block))) if last_height != Some(block.height.0) => {
+                    let height = block.height.0;
+                    let from = match last_height {
+                        Some(last) => (last + 1).max(height.saturating_sub(MAX_BLOCK_CATCH_UP - 1)),
+                        None => height,
+                    };
+                    for tx_height in from..=height {
+                        self.publish_tx_events(tx_height).await;
+                    }
+                    let _ = self.sender.send(ChainEvent::NewBlock {
+                        height,
+                        hash: block.hash.0.iter().map(|byte| format!("{:02X}", byte)).collect(),
+                        time: block.time.to_rfc3339(),
+                    });
+                    last_height = Some(height);
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!(error = %err, "ws poller failed to query latest block"),
+            }
+
+            match self.querier.query(RPCRequestType::QueryEpoch).await {
+                Ok(RPCResult::Epoch(epoch)) if last_epoch != Some(epoch.0) => {
+                    let _ = self.sender.send(ChainEvent::NewEpoch { epoch: epoch.0 });
+                    last_epoch = Some(epoch.0);
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!(error = %err, "ws poller failed to query epoch"),
+            }
+        }
+    }
+
+    async fn publish_tx_events(&self, height: u64) {
+        match self.querier.query(RPCRequestType::QueryBlockResults(BlockHeight(height))).await {
+            Ok(RPCResult::BlockResults(_, events)) => {
+                for event in events.into_iter().filter(|event| event.attributes.contains_key("hash")) {
+                    let _ = self.sender.send(ChainEvent::Tx {
+                        height,
+                        event_type: event.event_type,
+                        attributes: event.attributes,
+                    });
+                }
+            }
+            Ok(_) => {}
+            Err(err) => tracing::warn!(height, error = %err, "ws poller failed to query block results"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "topic", rename_all = "snake_case")]
+enum Topic {
+    Blocks,
+    Epochs,
+    Tx { hash: String },
+    Address { address: String },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ClientMessage {
+    Subscribe(Topic),
+    Unsubscribe(Topic),
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    blocks: bool,
+    epochs: bool,
+    tx_hashes: HashSet<String>,
+    addresses: HashSet<String>,
+}
+
+impl Subscriptions {
+    fn update(&mut self, topic: Topic, on: bool) {
+        match topic {
+            Topic::Blocks => self.blocks = on,
+            Topic::Epochs => self.epochs = on,
+            Topic::Tx { hash } => toggle(&mut self.tx_hashes, hash.to_uppercase(), on),
+            Topic::Address { address } => toggle(&mut self.addresses, address, on),
+        }
+    }
+
+    fn wants(&self, event: &ChainEvent) -> bool {
+        match event {
+            ChainEvent::NewBlock { .. } => self.blocks,
+            ChainEvent::NewEpoch { .. } => self.epochs,
+            ChainEvent::Tx { attributes, .. } => {
+                let by_hash = attributes
+                    .get("hash")
+                    .map_or(false, |hash| self.tx_hashes.contains(&hash.to_uppercase()));
+                // Addresses only show up inside attribute values (e.g. the changed keys of `inner_tx`)
+                let by_address = self
+                    .addresses
+                    .iter()
+                    .any(|address| attributes.values().any(|value| value.contains(address.as_str())));
+                by_hash || by_address
+            }
+        }
+    }
+}
+
+fn toggle(set: &mut HashSet<String>, value: String, on: bool) {
+    if on {
+        set.insert(value);
+    } else {
+        set.remove(&value);
+    }
+}
+
+pub async fn ws_handler(ws: WebSocketUpgrade, Extension(events): Extension<Arc<ChainEvents>>) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, events.subscribe()))
+}
+
+async fn handle_socket(mut socket: WebSocket, mut events: broadcast::Receiver<ChainEvent>) {
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let outgoing = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Subscribe(topic)) => {
+                        subscriptions.update(topic, true);
+                        json!({ "type": "subscribed" })
+                    }
+                    Ok(ClientMessage::Unsubscribe(topic)) => {
+                        subscriptions.update(topic, false);
+                        json!({ "type": "unsubscribed" })
+                    }
+                    Err(err) => json!({ "type": "error", "message": format!("Invalid message: {}", err) }),
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) if subscriptions.wants(&event) => json!(event),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(missed)) => {
+                    json!({ "type": "error", "message": format!("Client too slow, {} events dropped", missed) })
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        if socket.send(Message::Text(outgoing.to_string())).await.is_err() {
+            break;
+        }
+    }
+}
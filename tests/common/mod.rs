@@ -0,0 +1,207 @@
+//! A stand-in for a CometBFT node that answers `abci_query` JSON-RPC calls with canned
+//! storage values, and `block`/`block_results` with canned blocks, so the routes can be
+//! exercised without a live Namada chain.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::State, routing::post, Json, Router};
+use base64::Engine;
+use borsh::BorshSerialize;
+use namada_rest::{router, ServerState, Settings};
+use serde_json::{json, Value};
+use tendermint_rpc::HttpClient;
+
+#[derive(Clone, Default)]
+struct Fixtures {
+    // ABCI query path (e.g. "/shell/epoch") -> borsh-encoded response data
+    queries: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    // "block/<height>" or "block_results/<height>" -> JSON-RPC result
+    blocks: Arc<Mutex<HashMap<String, Value>>>,
+}
+
+// Clones share their fixtures, so a test can keep one to change answers while the node runs
+#[derive(Clone, Default)]
+pub struct MockRpc {
+    fixtures: Fixtures,
+}
+
+impl MockRpc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer queries for `path` with the borsh encoding of `value`.
+    pub fn with<T: BorshSerialize>(self, path: &str, value: &T) -> Self {
+        self.set(path, value);
+        self
+    }
+
+    /// Change the answer for `path`, also after the node has been spawned.
+    pub fn set<T: BorshSerialize>(&self, path: &str, value: &T) {
+        self.fixtures
+            .queries
+            .lock()
+            .unwrap()
+            .insert(normalize(path), borsh::to_vec(value).expect("fixture must encode"));
+    }
+
+    /// Serve a block at `height` holding `txs`, in their wire encoding.
+    pub fn with_block(self, height: u64, txs: &[Vec<u8>]) -> Self {
+        let hash = format!("{:064X}", height);
+        let txs = txs
+            .iter()
+            .map(|tx| base64::engine::general_purpose::STANDARD.encode(tx))
+            .collect::<Vec<_>>();
+        // Only the first block may come without the previous block's commit
+        let (last_block_id, last_commit) = if height == 1 {
+            (Value::Null, Value::Null)
+        } else {
+            let previous_hash = format!("{:064X}", height - 1);
+            let previous = json!({ "hash": previous_hash, "parts": { "total": 1, "hash": previous_hash } });
+            let commit = json!({
+                "height": (height - 1).to_string(),
+                "round": 0,
+                "block_id": previous,
+                "signatures": [],
+            });
+            (previous, commit)
+        };
+        let result = json!({
+            "block_id": { "hash": hash, "parts": { "total": 1, "hash": hash } },
+            "block": {
+                "header": {
+                    "version": { "block": "11", "app": "0" },
+                    "chain_id": "namada-mock",
+                    "height": height.to_string(),
+                    "time": "2024-01-01T00:00:00Z",
+                    "last_block_id": last_block_id,
+                    "last_commit_hash": "",
+                    "data_hash": "",
+                    "validators_hash": "",
+                    "next_validators_hash": "",
+                    "consensus_hash": "",
+                    "app_hash": "",
+                    "last_results_hash": "",
+                    "evidence_hash": "",
+                    "proposer_address": "0000000000000000000000000000000000000000",
+                },
+                "data": { "txs": txs },
+                "evidence": { "evidence": [] },
+                "last_commit": last_commit,
+            },
+        });
+        self.fixtures.blocks.lock().unwrap().insert(format!("block/{}", height), result);
+        self
+    }
+
+    /// Serve the results of the block at `height`, with `events` emitted at its end as
+    /// `(type, attributes)` pairs.
+    pub fn with_block_results(self, height: u64, events: &[(&str, &[(&str, &str)])]) -> Self {
+        let events = events
+            .iter()
+            .map(|(kind, attributes)| json!({
+                "type": kind,
+                "attributes": attributes
+                    .iter()
+                    .map(|(key, value)| json!({ "key": key, "value": value, "index": true }))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>();
+        let result = json!({
+            "height": height.to_string(),
+            "txs_results": null,
+            "begin_block_events": null,
+            "end_block_events": events,
+            "validator_updates": [],
+            "consensus_param_updates": null,
+        });
+        self.fixtures.blocks.lock().unwrap().insert(format!("block_results/{}", height), result);
+        self
+    }
+
+    /// Serve the fixtures on an ephemeral local port and return the node's URL.
+    pub async fn spawn(self) -> String {
+        let app = Router::new()
+            .route("/", post(handle_json_rpc))
+            .with_state(self.fixtures);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    /// Spawn the mock node and return a client for it with the settings to serve it with.
+    pub async fn into_client(self) -> (HttpClient, Settings) {
+        let rpc_url = self.spawn().await;
+        let config: Settings = toml::from_str(&format!(
+            "rpc_url = \"{}\"\nbind_ip = \"127.0.0.1\"\nport = 0\n",
+            rpc_url
+        ))
+        .unwrap();
+        (HttpClient::new(rpc_url.as_str()).unwrap(), config)
+    }
+
+    /// Spawn the mock node and build the REST router on top of it.
+    pub async fn into_app(self) -> axum::Router {
+        let (client, config) = self.into_client().await;
+        router(ServerState::new(client, config))
+    }
+}
+
+// Compare paths without empty segments, so fixtures don't depend on how the SDK renders an
+// omitted optional argument (e.g. the source in `/vp/pos/bonds_and_unbonds/{source}/{validator}`)
+fn normalize(path: &str) -> String {
+    path.split('/').filter(|segment| !segment.is_empty()).map(|segment| format!("/{}", segment)).collect()
+}
+
+fn rpc_error(id: Value, code: i64, message: &str, data: String) -> Json<Value> {
+    Json(json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message, "data": data },
+    }))
+}
+
+async fn handle_json_rpc(
+    State(fixtures): State<Fixtures>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let id = request["id"].clone();
+    let method = request["method"].as_str().unwrap_or_default();
+    match method {
+        "abci_query" => {}
+        "block" | "block_results" => {
+            let height = request["params"]["height"].as_str().unwrap_or_default();
+            let key = format!("{}/{}", method, height);
+            return match fixtures.blocks.lock().unwrap().get(&key) {
+                Some(result) => Json(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+                None => rpc_error(id, -32603, "Internal error", format!("no fixture for {}", key)),
+            };
+        }
+        _ => return rpc_error(id, -32601, "Method not found", String::new()),
+    }
+
+    let path = request["params"]["path"].as_str().unwrap_or_default();
+    let (code, info, value) = match fixtures.queries.lock().unwrap().get(&normalize(path)) {
+        Some(value) => (0, String::new(), base64::engine::general_purpose::STANDARD.encode(value)),
+        None => (1, format!("no fixture for {}", path), String::new()),
+    };
+    Json(json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": {
+            "response": {
+                "code": code,
+                "log": "",
+                "info": info,
+                "index": "0",
+                "key": "",
+                "value": value,
+                "proofOps": null,
+                "height": "0",
+                "codespace": "",
+            }
+        },
+    }))
+}
@@ -0,0 +1,1110 @@
+mod common;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use borsh::BorshDeserialize;
+use common::MockRpc;
+use futures::StreamExt;
+use namada_rest::client::RpcQuerier;
+use namada_rest::indexer::{self, store::IndexStore, IndexerSettings};
+use namada_rest::{router, ServerState};
+use namada_sdk::eth_bridge::storage::whitelist;
+use namada_sdk::events::{Event, EventLevel, EventType};
+use namada_sdk::gas::Gas;
+use namada_sdk::governance::parameters::GovernanceParameters;
+use namada_sdk::governance::storage::proposal::{ProposalType, StorageProposal};
+use namada_sdk::governance::utils::{ProposalResult, TallyResult, TallyType, Vote};
+use namada_sdk::masp::MaspTokenRewardData;
+use namada_sdk::masp_primitives::asset_type::AssetType;
+use namada_sdk::masp_primitives::transaction::components::I128Sum;
+use namada_sdk::parameters::storage as params_storage;
+use namada_sdk::parameters::storage::get_epochs_per_year_key;
+use namada_sdk::parameters::EpochDuration;
+use namada_sdk::proof_of_stake::types::{
+    BondDetails, BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, CommissionPair, UnbondDetails,
+    ValidatorMetaData, ValidatorState,
+};
+use namada_sdk::queries::shell::eth_bridge::Erc20FlowControl;
+use namada_sdk::proof_of_stake::storage_key::{last_pos_inflation_amount_key, liveness_sum_missed_votes_key};
+use namada_sdk::proof_of_stake::PosParams;
+use namada_sdk::state::{BlockHash, BlockHeight, Epoch, LastBlock};
+use namada_sdk::token::storage_key::{
+    balance_key, masp_last_inflation_key, masp_last_locked_amount_key, minted_balance_key,
+};
+use namada_sdk::tx::data::TxResult;
+use namada_sdk::types::account::{Account, AccountPublicKeysMap};
+use namada_sdk::types::address::{Address, InternalAddress};
+use namada_sdk::types::chain::ProposalBytes;
+use namada_sdk::types::dec::Dec;
+use namada_sdk::types::hash::Hash;
+use namada_sdk::types::key::{common, RefTo};
+use namada_sdk::types::ethereum_events::EthAddress;
+use namada_sdk::types::storage::{Key, PrefixValue};
+use namada_sdk::types::time::{DateTimeUtc, DurationSecs};
+use namada_sdk::types::token::{self, MaspDenom};
+use namada_sdk::types::uint::Uint;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const NAAN_ADDRESS: &str = "tnam1qxvg64psvhwumv3mwrrjfcz0h3t3274hwggyzcee";
+const VALIDATOR_ADDRESS: &str = "tnam1q8c5j8gjaw7yz2fllzputv2cfn9jjktlzgyezv44";
+const BOB_ADDRESS: &str = "tnam1qpg3k3rfe2qjr74l53j4c0naa8x34cza4gl78htw";
+const TX_HASH: &str = "3E0702526E372BEF45E0F21B1A0759826322AE26AF67D9F337CCE177ADE230E4";
+
+// ABCI path the SDK reads a raw storage value through
+fn storage_path(key: &Key) -> String {
+    format!("/shell/value/{}", key)
+}
+
+fn commission(rate: &str, max_change: &str) -> CommissionPair {
+    CommissionPair {
+        commission_rate: Dec::from_str(rate).unwrap(),
+        max_commission_change_per_epoch: Dec::from_str(max_change).unwrap(),
+    }
+}
+
+// An ed25519 key derived from a fixed seed
+fn public_key(seed: u8) -> common::PublicKey {
+    let mut bytes = [seed; 33];
+    bytes[0] = 0;
+    common::SecretKey::try_from_slice(&bytes).unwrap().ref_to()
+}
+
+fn applied_event(code: &str) -> Event {
+    Event {
+        event_type: EventType::Applied,
+        level: EventLevel::Tx,
+        attributes: [("hash".to_string(), TX_HASH.to_string()), ("code".to_string(), code.to_string())].into(),
+    }
+}
+
+fn bond(start: u64, amount: u64, slashed: Option<u64>) -> BondDetails {
+    BondDetails {
+        start: Epoch(start),
+        amount: token::Amount::from_u64(amount),
+        slashed_amount: slashed.map(token::Amount::from_u64),
+    }
+}
+
+fn unbond(start: u64, withdraw: u64, amount: u64) -> UnbondDetails {
+    UnbondDetails {
+        start: Epoch(start),
+        withdraw: Epoch(withdraw),
+        amount: token::Amount::from_u64(amount),
+        slashed_amount: None,
+    }
+}
+
+fn bonds_of(source: &str, bonds: Vec<BondDetails>, unbonds: Vec<UnbondDetails>) -> BondsAndUnbondsDetails {
+    let bond_id = BondId {
+        source: Address::decode(source).unwrap(),
+        validator: Address::decode(VALIDATOR_ADDRESS).unwrap(),
+    };
+    HashMap::from([(bond_id, BondsAndUnbondsDetail { bonds, unbonds, slashes: vec![] })])
+}
+
+async fn get(app: Router, uri: &str) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn post(app: Router, uri: &str, request: Value) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(
+            Request::post(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(request.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn epoch_returns_current_epoch() {
+    let app = MockRpc::new().with("/shell/epoch", &Epoch(23)).into_app().await;
+
+    let (status, body) = get(app, "/epoch").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "epoch": 23 }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn epoch_at_height_reports_unknown_height() {
+    let app = MockRpc::new()
+        .with("/shell/epoch_at_height/100", &Some(Epoch(4)))
+        .with("/shell/epoch_at_height/200", &Option::<Epoch>::None)
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), "/epoch_at_height/100").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "epoch": 4 }));
+
+    let (status, body) = get(app, "/epoch_at_height/200").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "epoch": "None" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn native_token_returns_address() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let app = MockRpc::new().with("/shell/native_token", &native_token).into_app().await;
+
+    let (status, body) = get(app, "/native_token").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "address": NAAN_ADDRESS }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn upstream_errors_become_500() {
+    let app = MockRpc::new().into_app().await;
+
+    let (status, body) = get(app, "/epoch").await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body["error"].is_string());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn baseline_routes_turn_upstream_errors_into_500() {
+    // No fixtures at all, so every query the routes make fails upstream
+    let app = MockRpc::new().into_app().await;
+
+    for uri in [
+        "/proposal_result/1".to_string(),
+        "/proposal_votes/1".to_string(),
+        "/epoch_at_height/100".to_string(),
+        format!("/balance/{}", BOB_ADDRESS),
+        format!("/validator_state/{}/7", VALIDATOR_ADDRESS),
+        format!("/delegator_delegation/{}", BOB_ADDRESS),
+        format!("/delegator_delegation_at/{}/7", BOB_ADDRESS),
+        format!("/metadata/{}/7", VALIDATOR_ADDRESS),
+        "/governance".to_string(),
+        "/pos_params".to_string(),
+        format!("/is_steward/{}", BOB_ADDRESS),
+        format!("/validator_consensus_keys/{}", VALIDATOR_ADDRESS),
+        format!("/tx_event/{}", TX_HASH),
+        "/native_token".to_string(),
+        "/query_block".to_string(),
+        format!("/is_validator/{}", VALIDATOR_ADDRESS),
+        format!("/is_delegator/{}", BOB_ADDRESS),
+        "/masp_reward".to_string(),
+        "/total_staked/7".to_string(),
+        format!("/validator_stake/{}/7", VALIDATOR_ADDRESS),
+    ] {
+        let (status, body) = get(app.clone(), &uri).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR, "{}", uri);
+        assert!(body["error"].is_string(), "{}", uri);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn balance_returns_the_native_token_balance() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let owner = Address::decode(BOB_ADDRESS).unwrap();
+    let app = MockRpc::new()
+        .with(&storage_path(&balance_key(&native_token, &owner)), &token::Amount::from_u64(1_500_000))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/balance/{}", BOB_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "balance": "1500000" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validator_state_names_the_state_or_explains_its_absence() {
+    let app = MockRpc::new()
+        .with(&format!("/vp/pos/validator/state/{}/7", VALIDATOR_ADDRESS), &Some(ValidatorState::BelowCapacity))
+        .with(&format!("/vp/pos/validator/state/{}/1", VALIDATOR_ADDRESS), &Option::<ValidatorState>::None)
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), &format!("/validator_state/{}/7", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "state": "BelowCapacity" }));
+
+    let (status, body) = get(app, &format!("/validator_state/{}/1", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["state"].as_str().unwrap().starts_with("Your validator is either not a validator"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn delegator_delegation_lists_validators() {
+    let validator = Address::decode(VALIDATOR_ADDRESS).unwrap();
+    let app = MockRpc::new()
+        .with(&format!("/vp/pos/delegations/{}", BOB_ADDRESS), &HashSet::from([validator]))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/delegator_delegation/{}", BOB_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "data": [VALIDATOR_ADDRESS] }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn delegator_delegation_at_reports_amounts_per_validator() {
+    let validator = Address::decode(VALIDATOR_ADDRESS).unwrap();
+    let app = MockRpc::new()
+        .with(
+            &format!("/vp/pos/delegations_at/{}/7", BOB_ADDRESS),
+            &HashMap::from([(validator, token::Amount::from_u64(2_000))]),
+        )
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/delegator_delegation_at/{}/7", BOB_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "data": { VALIDATOR_ADDRESS: "2000" } }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metadata_reports_the_commission_at_the_epoch() {
+    let app = MockRpc::new()
+        .with(&format!("/vp/pos/validator/metadata/{}", VALIDATOR_ADDRESS), &Option::<ValidatorMetaData>::None)
+        .with(&format!("/vp/pos/validator/commission/{}/7", VALIDATOR_ADDRESS), &Some(commission("0.05", "0.01")))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/metadata/{}/7", VALIDATOR_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({
+        "metadata": null,
+        "commission": { "commission_rate": "0.05", "max_commission_change_per_epoch": "0.01" },
+    }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn governance_returns_the_parameters_as_strings() {
+    let params = GovernanceParameters::default();
+    let app = MockRpc::new().with("/vp/governance/parameters", &params).into_app().await;
+
+    let (status, body) = get(app, "/governance").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["min_proposal_grace_epochs"], params.min_proposal_grace_epochs.to_string());
+    assert_eq!(body["data"]["max_proposal_code_size"], params.max_proposal_code_size.to_string());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pos_params_are_flattened_to_json() {
+    let params = PosParams::default();
+    let app = MockRpc::new().with("/vp/pos/pos_params", &params).into_app().await;
+
+    let (status, body) = get(app, "/pos_params").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["owned"]["pipeline_len"], params.owned.pipeline_len);
+    assert_eq!(body["data"]["owned"]["liveness_threshold"], params.owned.liveness_threshold.to_string());
+    assert_eq!(body["data"]["max_proposal_period"], params.max_proposal_period);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn proposal_result_reports_the_tally_and_threshold() {
+    let proposal = StorageProposal {
+        id: 1,
+        content: BTreeMap::new(),
+        author: Address::decode(BOB_ADDRESS).unwrap(),
+        r#type: ProposalType::Default(None),
+        voting_start_epoch: Epoch(2),
+        voting_end_epoch: Epoch(5),
+        grace_epoch: Epoch(6),
+    };
+    let result = ProposalResult {
+        result: TallyResult::Passed,
+        tally_type: TallyType::TwoThirds,
+        total_voting_power: token::Amount::from_u64(300),
+        total_yay_power: token::Amount::from_u64(210),
+        total_nay_power: token::Amount::from_u64(60),
+        total_abstain_power: token::Amount::from_u64(30),
+    };
+    let app = MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/vp/governance/proposal/1", &Some(proposal))
+        .with("/vp/governance/stored_proposal_result/1", &Some(result))
+        .with("/vp/governance/proposal/2", &Option::<StorageProposal>::None)
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), "/proposal_result/1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["result"], "passed");
+    assert_eq!(body["total_yay_power"], "210");
+    assert_eq!(body["threshold"], "200");
+
+    let (status, body) = get(app, "/proposal_result/2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "error": "proposal not found" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn proposal_votes_lists_votes() {
+    let app = MockRpc::new().with("/vp/governance/proposal/1/votes", &Vec::<Vote>::new()).into_app().await;
+
+    let (status, body) = get(app, "/proposal_votes/1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "data": [] }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn role_checks_return_booleans() {
+    let app = MockRpc::new()
+        .with(&format!("/vp/pgf/stewards/{}", BOB_ADDRESS), &true)
+        .with(&format!("/vp/pos/validator/is_validator/{}", VALIDATOR_ADDRESS), &true)
+        .with(&format!("/vp/pos/is_delegator/{}", BOB_ADDRESS), &false)
+        .into_app()
+        .await;
+
+    for (uri, expected) in [
+        (format!("/is_steward/{}", BOB_ADDRESS), true),
+        (format!("/is_validator/{}", VALIDATOR_ADDRESS), true),
+        (format!("/is_delegator/{}", BOB_ADDRESS), false),
+    ] {
+        let (status, body) = get(app.clone(), &uri).await;
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        assert_eq!(body, json!({ "data": expected }), "{}", uri);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validator_consensus_keys_returns_the_key() {
+    let app = MockRpc::new()
+        .with(&format!("/vp/pos/validator/consensus_key/{}", VALIDATOR_ADDRESS), &Some(public_key(1)))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/validator_consensus_keys/{}", VALIDATOR_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body["data"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tx_event_returns_the_applied_event() {
+    let app = MockRpc::new()
+        .with(&format!("/shell/applied/{}", TX_HASH), &Some(applied_event("0")))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/tx_event/{}", TX_HASH)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["event_type"], "Applied");
+    assert_eq!(body["data"]["attributes"]["code"], "0");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn query_block_returns_the_last_committed_block() {
+    let last_block = LastBlock {
+        height: BlockHeight(42),
+        hash: BlockHash([7; 32]),
+        time: DateTimeUtc::from_str("2024-01-01T00:00:00Z").unwrap(),
+    };
+    let app = MockRpc::new().with("/shell/last_block", &Some(last_block)).into_app().await;
+
+    let (status, body) = get(app, "/query_block").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["height"], 42);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn masp_reward_lists_the_reward_tokens() {
+    let reward = MaspTokenRewardData {
+        name: "naan".to_string(),
+        address: Address::decode(NAAN_ADDRESS).unwrap(),
+        max_reward_rate: Dec::from_str("0.1").unwrap(),
+        kp_gain: Dec::from_str("0.25").unwrap(),
+        kd_gain: Dec::from_str("0.25").unwrap(),
+        locked_amount_target: Uint::from(10_000_000u64),
+    };
+    let app = MockRpc::new().with("/shell/masp_reward_tokens", &vec![reward]).into_app().await;
+
+    let (status, body) = get(app, "/masp_reward").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["name"], "naan");
+    assert_eq!(body["data"][0]["address"], NAAN_ADDRESS);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn total_and_validator_stake_are_reported_per_epoch() {
+    let app = MockRpc::new()
+        .with("/vp/pos/total_stake/7", &token::Amount::from_u64(1_000_000))
+        .with(&format!("/vp/pos/validator/stake/{}/7", VALIDATOR_ADDRESS), &Some(token::Amount::from_u64(250_000)))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), "/total_staked/7").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "total": "1000000" }));
+
+    let (status, body) = get(app, &format!("/validator_stake/{}/7", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "total": "250000" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn indexer_follows_blocks_from_the_mock_node() {
+    let last_block = LastBlock {
+        height: BlockHeight(1),
+        hash: BlockHash([1; 32]),
+        time: DateTimeUtc::from_str("2024-01-01T00:00:00Z").unwrap(),
+    };
+    let (client, config) = MockRpc::new()
+        .with("/shell/last_block", &Some(last_block))
+        .with_block(1, &[])
+        .with_block_results(1, &[("applied", &[("hash", TX_HASH), ("code", "0")])])
+        .into_client()
+        .await;
+    let store = IndexStore::open(":memory:").unwrap();
+    let settings = IndexerSettings { poll_interval_ms: 10, ..IndexerSettings::default() };
+    indexer::spawn(RpcQuerier::new(client.clone()), store.clone(), &settings);
+    let app = router(ServerState::new(client, config).with_index(store));
+
+    let mut last_indexed_height = Value::Null;
+    for _ in 0..100 {
+        let (status, body) = get(app.clone(), "/index/status").await;
+        assert_eq!(status, StatusCode::OK);
+        last_indexed_height = body["last_indexed_height"].clone();
+        if !last_indexed_height.is_null() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(last_indexed_height, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_returns_results_in_order_with_per_item_errors() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let app = MockRpc::new()
+        .with("/shell/epoch", &Epoch(23))
+        .with("/shell/native_token", &native_token)
+        .into_app()
+        .await;
+
+    let request = json!({
+        "queries": [
+            { "query": "native_token" },
+            { "query": "epoch" },
+            { "query": "no_such_query" },
+            { "query": "governance_parameters" },
+        ]
+    });
+    let response = app
+        .oneshot(
+            Request::post("/batch")
+                .header("content-type", "application/json")
+                .body(Body::from(request.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data.len(), 4);
+    assert_eq!(data[0], json!({ "ok": { "address": NAAN_ADDRESS } }));
+    assert_eq!(data[1], json!({ "ok": { "epoch": 23 } }));
+    assert!(data[2]["error"].as_str().unwrap().starts_with("Invalid query"));
+    assert!(data[3]["error"].is_string());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn graphql_rejects_queries_over_the_complexity_limit() {
+    let app = MockRpc::new().with("/shell/epoch", &Epoch(23)).into_app().await;
+
+    let fields = (0..300).map(|i| format!("e{}: epoch", i)).collect::<Vec<_>>().join(" ");
+    let (status, body) = post(app, "/graphql", json!({ "query": format!("{{ {} }}", fields) })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].is_null());
+    assert!(body["errors"][0]["message"].as_str().unwrap().contains("too complex"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn request_id_is_propagated() {
+    let app = MockRpc::new().with("/shell/epoch", &Epoch(23)).into_app().await;
+
+    let response = app
+        .oneshot(
+            Request::get("/epoch")
+                .header("x-request-id", "test-request")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.headers()["x-request-id"], "test-request");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn openapi_document_lists_routes() {
+    let app = MockRpc::new().into_app().await;
+
+    let (status, body) = get(app, "/openapi.json").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["paths"]["/is_validator/{wallet}"].is_object());
+    assert!(body["paths"]["/validator_stake/{address}/{epoch}"].is_object());
+    let stream = &body["paths"]["/tx_event/{tx_hash}/stream"]["get"];
+    assert!(stream["responses"]["200"]["content"]["text/event-stream"].is_object());
+    let epoch_at_height = &body["components"]["schemas"]["EpochAtHeightResponse"]["properties"]["epoch"];
+    assert_eq!(epoch_at_height["oneOf"][1]["enum"], json!(["None"]));
+    assert!(body["info"]["description"].as_str().unwrap().contains("/graphql"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tx_event_stream_goes_from_pending_to_applied() {
+    let applied_path = format!("/shell/applied/{}", TX_HASH);
+    let node = MockRpc::new()
+        .with(&applied_path, &Option::<Event>::None)
+        .with(&format!("/shell/accepted/{}", TX_HASH), &Option::<Event>::None);
+    let app = node.clone().into_app().await;
+
+    let response = app
+        .oneshot(Request::get(format!("/tx_event/{}/stream", TX_HASH)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let mut body = response.into_body().into_data_stream();
+
+    let first = body.next().await.unwrap().unwrap();
+    assert!(String::from_utf8_lossy(&first).starts_with("event: pending\n"));
+
+    node.set(&applied_path, &Some(applied_event("0")));
+
+    // The stream ends after `applied`, so this collects everything that is left
+    let rest = body
+        .map(|chunk| String::from_utf8_lossy(&chunk.unwrap()).into_owned())
+        .collect::<String>()
+        .await;
+    let events = rest.lines().filter_map(|line| line.strip_prefix("event: ")).collect::<Vec<_>>();
+    assert_eq!(events, vec!["applied"]);
+    assert!(rest.contains("\"code\":\"0\""));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn account_txs_require_the_indexer() {
+    let app = MockRpc::new().into_app().await;
+
+    let (status, body) = get(app, &format!("/account/{}/txs", NAAN_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body["error"].as_str().unwrap().contains("indexer"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn shielded_balance_is_not_found_when_disabled() {
+    let app = MockRpc::new().into_app().await;
+
+    let (status, body) = post(app, "/shielded/balance", json!({ "viewing_key": "zvknam1..." })).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].as_str().unwrap().contains("[shielded]"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn estimate_fee_rejects_unknown_kinds() {
+    let app = MockRpc::new().into_app().await;
+
+    let (status, body) = post(app, "/tx/estimate_fee", json!({ "kind": "no_such_tx" })).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("transfer"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn estimate_fee_prices_table_gas_in_every_fee_token() {
+    let gas_prices = BTreeMap::from([
+        (Address::decode(NAAN_ADDRESS).unwrap(), token::Amount::from_u64(100)),
+        (Address::decode(VALIDATOR_ADDRESS).unwrap(), token::Amount::from_u64(3)),
+    ]);
+    let app = MockRpc::new()
+        .with(&storage_path(&params_storage::get_gas_cost_key()), &gas_prices)
+        .into_app()
+        .await;
+
+    let (status, body) = post(app, "/tx/estimate_fee", json!({ "kind": "bond" })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["source"], "table");
+    assert_eq!(body["gas"], 30_000);
+    assert_eq!(body["fees"], json!({ NAAN_ADDRESS: "3000000", VALIDATOR_ADDRESS: "90000" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn estimate_fee_reports_dry_run_gas_in_whole_units() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let gas_prices = BTreeMap::from([(native_token, token::Amount::from_u64(100))]);
+    let dry_run = TxResult { gas_used: Gas::from(72_622_035), ..TxResult::default() };
+    let app = MockRpc::new()
+        .with("/shell/dry_run_tx", &dry_run)
+        .with(&storage_path(&params_storage::get_gas_cost_key()), &gas_prices)
+        .into_app()
+        .await;
+
+    let (status, body) = post(app, "/tx/estimate_fee", json!({ "tx": "0x00" })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["source"], "dry_run");
+    assert_eq!(body["gas"], 7263);
+    assert_eq!(body["fees"], json!({ NAAN_ADDRESS: "726300" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn account_combines_roles_bonds_and_pending_unbonds() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let owner = Address::decode(BOB_ADDRESS).unwrap();
+    let bonds = bonds_of(
+        BOB_ADDRESS,
+        vec![bond(3, 1_000, Some(100)), bond(6, 500, None)],
+        // Withdrawable in the past, right now and in two epochs
+        vec![unbond(2, 9, 40), unbond(3, 10, 50), unbond(5, 12, 60)],
+    );
+    let app = MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/shell/native_token", &native_token)
+        .with("/shell/masp_reward_tokens", &Vec::<MaspTokenRewardData>::new())
+        .with(&format!("/shell/account/{}", BOB_ADDRESS), &Option::<Account>::None)
+        .with(&format!("/vp/pos/validator/is_validator/{}", BOB_ADDRESS), &false)
+        .with(&format!("/vp/pos/is_delegator/{}", BOB_ADDRESS), &true)
+        .with(&format!("/vp/pgf/stewards/{}", BOB_ADDRESS), &false)
+        .with(&format!("/vp/pos/bonds_and_unbonds/{}", BOB_ADDRESS), &bonds)
+        .with(&storage_path(&balance_key(&native_token, &owner)), &token::Amount::from_u64(7_000))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/account/{}", BOB_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["kind"], "implicit");
+    assert_eq!(body["roles"], json!({ "validator": false, "delegator": true, "steward": false }));
+    assert_eq!(body["balances"], json!({ NAAN_ADDRESS: "7000" }));
+    // 1000 bonded with 100 slashed, plus 500
+    assert_eq!(body["total_bonded"], "1400");
+    assert_eq!(body["pending_unbonds"], json!([
+        { "validator": VALIDATOR_ADDRESS, "amount": "60", "withdrawable_epoch": 12 },
+    ]));
+    assert!(body["public_keys"].is_null());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn account_keys_are_listed_by_index_with_the_threshold_and_vp() {
+    let multisig = Address::decode(VALIDATOR_ADDRESS).unwrap();
+    let keys = vec![public_key(3), public_key(1), public_key(2)];
+    let account = Account {
+        public_keys_map: AccountPublicKeysMap::from_iter(keys.clone()),
+        threshold: 2,
+        address: multisig.clone(),
+    };
+    let vp_code_hash = Hash::sha256(b"vp_user");
+    let app = MockRpc::new()
+        .with(&format!("/shell/account/{}", VALIDATOR_ADDRESS), &Some(account))
+        .with(&storage_path(&Key::validity_predicate(&multisig)), &vp_code_hash)
+        .with(&format!("/shell/account/{}", BOB_ADDRESS), &Option::<Account>::None)
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), &format!("/account/{}/keys", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["kind"], "established");
+    assert_eq!(body["threshold"], 2);
+    assert_eq!(body["public_keys"], json!([
+        { "index": 0, "public_key": keys[0].to_string() },
+        { "index": 1, "public_key": keys[1].to_string() },
+        { "index": 2, "public_key": keys[2].to_string() },
+    ]));
+    assert_eq!(body["vp_code_hash"], vp_code_hash.to_string());
+
+    // Implicit accounts run the built-in VP and have no stored code hash
+    let (status, body) = get(app, &format!("/account/{}/keys", BOB_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["kind"], "implicit");
+    assert!(body["threshold"].is_null());
+    assert_eq!(body["public_keys"], json!([]));
+    assert!(body["vp_code_hash"].is_null());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn delegators_at_past_epochs_add_back_unbonded_tokens() {
+    // With the default parameters an unbond leaves its bond unbonding_len (21) plus
+    // cubic_slashing_window_length (1) epochs before it becomes withdrawable
+    let params = PosParams::default();
+    assert_eq!(params.owned.unbonding_len + params.owned.cubic_slashing_window_length, 22);
+    let mut bonds = bonds_of(
+        NAAN_ADDRESS,
+        // 400 of a 1000 bond unbonded at epoch 10, leaving the bond at epoch 12
+        vec![bond(2, 600, None)],
+        vec![unbond(2, 34, 400)],
+    );
+    bonds.extend(bonds_of(
+        BOB_ADDRESS,
+        // A 300 bond fully unbonded at epoch 4, leaving the bond at epoch 6
+        vec![bond(5, 500, None)],
+        vec![unbond(1, 28, 300)],
+    ));
+    let app = MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/vp/pos/pos_params", &params)
+        .with(&format!("/vp/pos/bonds_and_unbonds/{}", VALIDATOR_ADDRESS), &bonds)
+        .into_app()
+        .await;
+    let amounts = |body: &Value| {
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| (row["delegator"].as_str().unwrap().to_string(), row["amount"].as_str().unwrap().to_string()))
+            .collect::<Vec<_>>()
+    };
+    let row = |delegator: &str, amount: &str| (delegator.to_string(), amount.to_string());
+
+    // The 400 still counts until the pipeline epoch of its unbonding
+    let (status, body) = get(app.clone(), &format!("/validator/{}/delegators", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(amounts(&body), vec![row(NAAN_ADDRESS, "1000"), row(BOB_ADDRESS, "500")]);
+
+    let (_, body) = get(app.clone(), &format!("/validator/{}/delegators?epoch=12", VALIDATOR_ADDRESS)).await;
+    assert_eq!(amounts(&body), vec![row(NAAN_ADDRESS, "600"), row(BOB_ADDRESS, "500")]);
+
+    let (_, body) = get(app.clone(), &format!("/validator/{}/delegators?epoch=5", VALIDATOR_ADDRESS)).await;
+    assert_eq!(amounts(&body), vec![row(NAAN_ADDRESS, "1000"), row(BOB_ADDRESS, "800")]);
+
+    let (_, body) = get(app, &format!("/validator/{}/delegators?epoch=3", VALIDATOR_ADDRESS)).await;
+    assert_eq!(body["total"], 2);
+    assert_eq!(amounts(&body), vec![row(NAAN_ADDRESS, "1000"), row(BOB_ADDRESS, "300")]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn liveness_counts_down_to_the_jailing_limit() {
+    let validator = Address::decode(VALIDATOR_ADDRESS).unwrap();
+    let mut params = PosParams::default();
+    params.owned.liveness_window_check = 100;
+    params.owned.liveness_threshold = Dec::from_str("0.9").unwrap();
+    let missed_votes_key = liveness_sum_missed_votes_key()
+        .push(&"data".to_owned())
+        .and_then(|key| key.push(&validator))
+        .unwrap();
+    let node = MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/vp/pos/pos_params", &params)
+        .with(&format!("/vp/pos/validator/state/{}/10", VALIDATOR_ADDRESS), &Some(ValidatorState::Consensus))
+        .with(&storage_path(&missed_votes_key), &8u64);
+    let app = node.clone().into_app().await;
+
+    let (status, body) = get(app.clone(), &format!("/validator/{}/liveness", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["state"], "Consensus");
+    assert_eq!(body["missed_votes"], 8);
+    assert_eq!(body["max_missed_votes"], 10);
+    assert_eq!(body["remaining_missed_votes"], 1);
+
+    // The next miss reaches the limit, so none are left
+    node.set(&storage_path(&missed_votes_key), &9u64);
+    let (_, body) = get(app, &format!("/validator/{}/liveness", VALIDATOR_ADDRESS)).await;
+    assert_eq!(body["missed_votes"], 9);
+    assert_eq!(body["remaining_missed_votes"], 0);
+}
+
+fn assert_close(actual: &Value, expected: f64) {
+    let actual = actual.as_f64().unwrap();
+    assert!((actual - expected).abs() < 1e-12, "{} != {}", actual, expected);
+}
+
+// A chain at epoch 10 with 1000 NAAN of PoS inflation in the last epoch, 365 epochs a year, a
+// 10% max inflation rate and 1_000_000 NAAN minted, of which `total_staked` is staked
+fn staking_chain(total_staked: token::Amount) -> MockRpc {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let mut params = PosParams::default();
+    params.owned.max_inflation_rate = Dec::from_str("0.1").unwrap();
+    MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/shell/native_token", &native_token)
+        .with("/vp/pos/pos_params", &params)
+        .with(&storage_path(&get_epochs_per_year_key()), &365u64)
+        .with(&storage_path(&last_pos_inflation_amount_key()), &token::Amount::from_u64(1_000))
+        .with(&storage_path(&minted_balance_key(&native_token)), &token::Amount::from_u64(1_000_000))
+        .with("/vp/pos/total_stake/10", &total_staked)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn staking_apr_extrapolates_the_last_inflation() {
+    let app = staking_chain(token::Amount::from_u64(500_000))
+        .with(&format!("/vp/pos/validator/metadata/{}", VALIDATOR_ADDRESS), &Option::<ValidatorMetaData>::None)
+        .with(&format!("/vp/pos/validator/commission/{}/10", VALIDATOR_ADDRESS), &Some(commission("0.1", "0.01")))
+        .with(&format!("/vp/pos/validator/stake/{}/10", VALIDATOR_ADDRESS), &Some(token::Amount::from_u64(250_000)))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), "/staking/apr").await;
+    assert_eq!(status, StatusCode::OK);
+    // 1000 * 365 / 500_000
+    assert_close(&body["apr"], 0.73);
+    // 0.1 * 1_000_000 / 500_000
+    assert_close(&body["max_apr"], 0.2);
+    assert_close(&body["inputs"]["staked_ratio"], 0.5);
+    assert_eq!(body["inputs"]["epochs_per_year"], 365);
+
+    let (status, body) = get(app, &format!("/staking/apr/{}", VALIDATOR_ADDRESS)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_close(&body["commission_rate"], 0.1);
+    assert_close(&body["gross_apr"], 0.73);
+    assert_close(&body["apr"], 0.73 * 0.9);
+    assert_eq!(body["stake"], "250000");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn staking_apr_is_zero_without_stake() {
+    let app = staking_chain(token::Amount::zero()).into_app().await;
+
+    let (status, body) = get(app, "/staking/apr").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_close(&body["apr"], 0.0);
+    assert_close(&body["max_apr"], 0.0);
+    assert_close(&body["inputs"]["staked_ratio"], 0.0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn staking_apr_handles_stakes_beyond_u128() {
+    let huge = token::Amount::from_uint(Uint::from(u128::MAX) * Uint::from(4u64), 0).unwrap();
+    let app = staking_chain(huge).into_app().await;
+
+    let (status, body) = get(app, "/staking/apr").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["apr"].as_f64().unwrap() < 1e-30);
+}
+
+// Minted supply and holdings of `token` on a chain whose native token is NAAN
+fn supply_chain(token: &Address) -> MockRpc {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let holdings = [
+        (InternalAddress::PoS, 400_000),
+        (InternalAddress::Masp, 50_000),
+        (InternalAddress::Pgf, 7_000),
+    ];
+    holdings.into_iter().fold(
+        MockRpc::new()
+            .with("/shell/epoch", &Epoch(10))
+            .with("/shell/native_token", &native_token)
+            .with(&storage_path(&minted_balance_key(token)), &token::Amount::from_u64(1_000_000))
+            .with(&storage_path(&masp_last_inflation_key(token)), &token::Amount::from_u64(30)),
+        |node, (owner, amount)| {
+            node.with(&storage_path(&balance_key(token, &Address::Internal(owner))), &token::Amount::from_u64(amount))
+        },
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn supply_lists_locked_balances_and_native_pos_inflation() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let app = supply_chain(&native_token)
+        .with(&storage_path(&last_pos_inflation_amount_key()), &token::Amount::from_u64(1_200))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, &format!("/supply/{}", NAAN_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["token"], NAAN_ADDRESS);
+    assert_eq!(body["epoch"], 10);
+    // The holdings are reported next to the minted supply, not subtracted from it
+    assert_eq!(body["total_supply"], "1000000");
+    assert_eq!(body["locked_in_pos"], "400000");
+    assert_eq!(body["locked_in_masp"], "50000");
+    assert_eq!(body["pgf_treasury"], "7000");
+    assert_eq!(body["last_inflation"], json!({ "pos": "1200", "masp": "30" }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn supply_of_other_tokens_has_no_pos_inflation() {
+    // No PoS inflation fixture: it must not be queried for a non-native token
+    let token = Address::decode(VALIDATOR_ADDRESS).unwrap();
+    let app = supply_chain(&token).into_app().await;
+
+    let (status, body) = get(app, &format!("/supply/{}", VALIDATOR_ADDRESS)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_supply"], "1000000");
+    assert_eq!(body["locked_in_masp"], "50000");
+    assert!(body["last_inflation"]["pos"].is_null());
+    assert_eq!(body["last_inflation"]["masp"], "30");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn masp_conversions_are_filtered_by_epoch() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let asset = |name: &str| AssetType::new(name.as_bytes()).unwrap();
+    let (old, new, reward) = (asset("naan-0-9"), asset("naan-0-10"), asset("naan-0-10-reward"));
+    let conversions = BTreeMap::from([
+        (old, (native_token.clone(), MaspDenom::Zero, Epoch(9), I128Sum::from_pair(new, 1) + I128Sum::from_pair(reward, 5))),
+        (new, (native_token.clone(), MaspDenom::Zero, Epoch(10), I128Sum::zero())),
+        (asset("naan-1-10"), (native_token, MaspDenom::One, Epoch(10), I128Sum::zero())),
+    ]);
+    let app = MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/shell/conversions", &conversions)
+        .into_app()
+        .await;
+
+    let (status, body) = get(app.clone(), "/masp/conversions").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["epoch"], 10);
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data.len(), 2);
+    assert!(data.iter().all(|conversion| conversion["epoch"] == 10));
+    assert_eq!(
+        data.iter().map(|conversion| conversion["denom"].as_u64().unwrap()).collect::<HashSet<_>>(),
+        HashSet::from([0, 1])
+    );
+
+    let (status, body) = get(app, "/masp/conversions?epoch=9").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([{
+        "asset_type": old.to_string(),
+        "token": NAAN_ADDRESS,
+        "denom": 0,
+        "epoch": 9,
+        "conversion": { new.to_string(): "1", reward.to_string(): "5" },
+    }]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn masp_rewards_annualise_the_last_epoch_rate() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let reward = MaspTokenRewardData {
+        name: "naan".to_string(),
+        address: native_token.clone(),
+        max_reward_rate: Dec::from_str("0.1").unwrap(),
+        kp_gain: Dec::from_str("0.25").unwrap(),
+        kd_gain: Dec::from_str("0.25").unwrap(),
+        locked_amount_target: Uint::from(10_000_000u64),
+    };
+    let masp = Address::Internal(InternalAddress::Masp);
+    let app = MockRpc::new()
+        .with("/shell/epoch", &Epoch(10))
+        .with("/shell/masp_reward_tokens", &vec![reward])
+        .with(&storage_path(&get_epochs_per_year_key()), &365u64)
+        .with(&storage_path(&balance_key(&native_token, &masp)), &token::Amount::from_u64(60_000))
+        .with(&storage_path(&masp_last_locked_amount_key(&native_token)), &token::Amount::from_u64(50_000))
+        .with(&storage_path(&masp_last_inflation_key(&native_token)), &token::Amount::from_u64(100))
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, "/masp/rewards").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["epochs_per_year"], 365);
+    let reward = &body["data"][0];
+    assert_eq!(reward["locked_amount"], "60000");
+    // The rate is computed against what was locked when the inflation was minted
+    assert_close(&reward["epoch_reward_rate"], 100.0 / 50_000.0);
+    assert_close(&reward["annual_reward_rate"], 100.0 / 50_000.0 * 365.0);
+    assert_close(&reward["max_reward_rate"], 0.1);
+    assert_eq!(reward["locked_amount_target"], "10000000");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bridge_whitelist_reports_the_flow_control_of_each_erc20() {
+    let erc20 = EthAddress([0x11; 20]);
+    let entry = |suffix| Key::from(&whitelist::Key { asset: erc20, suffix });
+    let whitelisted = entry(whitelist::KeyType::Whitelisted);
+    let prefix = whitelisted.parent().and_then(|asset| asset.parent()).unwrap();
+    // Both of the token's entries fall under the prefix, but its flow control is queried once
+    let entries = vec![
+        PrefixValue { key: whitelisted, value: borsh::to_vec(&true).unwrap() },
+        PrefixValue {
+            key: entry(whitelist::KeyType::Cap),
+            value: borsh::to_vec(&token::Amount::from_u64(5_000)).unwrap(),
+        },
+    ];
+    let flow_control = Erc20FlowControl {
+        whitelisted: true,
+        supply: token::Amount::from_u64(1_200),
+        cap: token::Amount::from_u64(5_000),
+    };
+    let app = MockRpc::new()
+        .with(&format!("/shell/prefix/{}", prefix), &entries)
+        .with(&format!("/shell/eth_bridge/erc20/flow_control/{}", erc20), &flow_control)
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, "/eth_bridge/whitelist").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([{
+        "erc20": erc20.to_canonical(),
+        "whitelisted": true,
+        "supply": "1200",
+        "cap": "5000",
+    }]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn parameters_decode_each_value_with_its_storage_type() {
+    let native_token = Address::decode(NAAN_ADDRESS).unwrap();
+    let implicit_vp = Hash::sha256(b"vp_implicit");
+    let epoch_duration = EpochDuration { min_num_of_blocks: 10, min_duration: DurationSecs(86_400) };
+    let app = MockRpc::new()
+        .with(&storage_path(&params_storage::get_epoch_duration_storage_key()), &epoch_duration)
+        .with(&storage_path(&params_storage::get_max_expected_time_per_block_key()), &DurationSecs(30))
+        .with(&storage_path(&params_storage::get_epochs_per_year_key()), &365u64)
+        .with(&storage_path(&params_storage::get_max_block_gas_key()), &20_000_000u64)
+        .with(&storage_path(&params_storage::get_max_tx_bytes_key()), &1_048_576u32)
+        .with(&storage_path(&params_storage::get_max_proposal_bytes_key()), &ProposalBytes::new(6_291_456).unwrap())
+        .with(
+            &storage_path(&params_storage::get_gas_cost_key()),
+            &BTreeMap::from([(native_token, token::Amount::from_u64(100))]),
+        )
+        .with(&storage_path(&params_storage::get_implicit_vp_key()), &implicit_vp)
+        .with(&storage_path(&params_storage::get_tx_whitelist_storage_key()), &Vec::<String>::new())
+        .with(&storage_path(&params_storage::get_vp_whitelist_storage_key()), &vec![implicit_vp.to_string()])
+        .into_app()
+        .await;
+
+    let (status, body) = get(app, "/parameters").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({
+        "epoch_duration": { "min_num_of_blocks": 10, "min_duration_secs": 86_400 },
+        "max_expected_time_per_block_secs": 30,
+        "epochs_per_year": 365,
+        "max_block_gas": 20_000_000,
+        "max_tx_bytes": 1_048_576,
+        "max_proposal_bytes": 6_291_456,
+        "fee_tokens": [NAAN_ADDRESS],
+        "min_gas_prices": { NAAN_ADDRESS: "100" },
+        "implicit_vp_code_hash": implicit_vp.to_string(),
+        "tx_whitelist": [],
+        "vp_whitelist": [implicit_vp.to_string()],
+    }));
+}